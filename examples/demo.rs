@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use phys64::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+        .add_plugins(RapierDebugRenderPlugin::default())
+        .add_plugins(Phys64Plugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, record_and_replay)
+        .run();
+}
+
+const REPLAY_PATH: &str = "replay.ron";
+
+/// F9 starts and stops recording to `replay.ron`, F10 plays it back
+fn record_and_replay(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    fixed_time: Res<Time<Fixed>>,
+    player_query: Query<(Entity, Option<&InputRecorder>), With<Player>>,
+) {
+    for (entity, recorder) in &player_query {
+        if keys.just_pressed(KeyCode::F9) {
+            match recorder {
+                Some(recorder) => {
+                    let replay = recorder.to_replay(fixed_time.timestep().as_secs_f64());
+                    match replay.save(REPLAY_PATH) {
+                        Ok(()) => info!("Saved {} frames to {REPLAY_PATH}", replay.frames.len()),
+                        Err(error) => error!("{error}"),
+                    }
+                    commands.entity(entity).remove::<InputRecorder>();
+                }
+                None => {
+                    commands.entity(entity).insert(InputRecorder::default());
+                }
+            }
+        }
+
+        if keys.just_pressed(KeyCode::F10) {
+            match Replay::load(REPLAY_PATH) {
+                Ok(replay) => {
+                    commands.entity(entity).insert(InputPlayback::new(&replay));
+                }
+                Err(error) => error!("{error}"),
+            }
+        }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    commands.spawn((
+        PbrBundle {
+            material: materials.add(Color::LIME_GREEN.into()),
+            mesh: meshes.add(shape::Capsule { ..default() }.into()),
+            ..default()
+        },
+        Player,
+        Collider::capsule_y(0.5, 0.5),
+        KinematicCharacterController {
+            offset: CharacterLength::Absolute(0.01),
+            snap_to_ground: Some(CharacterLength::Absolute(10.0)),
+            autostep: Some(CharacterAutostep {
+                max_height: CharacterLength::Absolute(0.5),
+                min_width: CharacterLength::Absolute(0.25),
+                include_dynamic_bodies: true,
+            }),
+            ..default()
+        },
+        MoveDirection::default(),
+        Momentum::default(),
+        Speed::default(),
+        Forces::default(),
+        GravityAffected,
+        GroundSensor::default(),
+        InputBuffer::default(),
+        Jumper::default(),
+        (
+            Carrier::default(),
+            WallSensor::default(),
+            GroundPounder::default(),
+            Diver::default(),
+            LedgeGrabber::default(),
+        ),
+        InputListenerBundle::input_map(),
+    ));
+
+    commands.spawn((
+        PbrBundle {
+            material: materials.add(Color::WHITE.into()),
+            mesh: meshes.add(shape::Box::new(50.0, 0.5, 50.0).into()),
+            transform: Transform::from_translation(Vec3::new(0.0, -2.0, 0.0))
+                .with_rotation(Quat::from_axis_angle(Vec3::Z, 30.0_f32.to_radians())),
+            ..default()
+        },
+        Collider::cuboid(25.0, 0.25, 25.0),
+        RigidBody::Fixed,
+    ));
+
+    commands.spawn((
+        PbrBundle {
+            material: materials.add(Color::ORANGE.into()),
+            mesh: meshes.add(shape::Cube::new(0.5).into()),
+            transform: Transform::from_translation(Vec3::new(0.0, 1.0, -2.0)),
+            ..default()
+        },
+        Collider::cuboid(0.25, 0.25, 0.25),
+        RigidBody::Dynamic,
+        Carryable {
+            weight: CarryWeight::Light,
+            offset: Vec3::new(0.0, 0.5, -0.9),
+        },
+    ));
+
+    // A gusty crosswind over part of the slope
+    commands.spawn((
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 1.0, 6.0)),
+        Collider::cuboid(3.0, 3.0, 3.0),
+        Sensor,
+        WindZone::new(Vec3::NEG_X, 6.0)
+            .with_turbulence(0.4)
+            .with_airborne_scale(1.5),
+    ));
+}
@@ -128,7 +128,7 @@ fn rotate_camera(
                     let angle_i16 = camera.angle as i16;
                     let angle_difference = angle_i16 % 45;
                     let angle_change = if angle_difference <= 22 {
-                        -1 * angle_difference
+                        -angle_difference
                     } else {
                         45 - angle_difference
                     };
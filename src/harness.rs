@@ -0,0 +1,327 @@
+use crate::{
+    input::{InputBuffer, InputFrame, PlayerAction},
+    player::Player,
+    replay::{play_back_input, record_input, Replay},
+    types::*,
+    Phys64Plugin,
+};
+use bevy::{
+    ecs::schedule::{ExecutorKind, LogLevel, ScheduleBuildSettings, ScheduleLabel},
+    prelude::*,
+    scene::ScenePlugin,
+    time::TimeUpdateStrategy,
+};
+use bevy_rapier3d::prelude::*;
+use std::{collections::VecDeque, time::Duration};
+
+/// Input for a harness character, consumed one frame per fixed tick. Once the queue runs dry the
+/// last frame is held.
+#[derive(Component, Default)]
+pub struct ScriptedInput {
+    queue: VecDeque<InputFrame>,
+    current: InputFrame,
+}
+
+impl ScriptedInput {
+    pub fn push(&mut self, frame: InputFrame) {
+        self.queue.push_back(frame);
+    }
+
+    pub fn hold(&mut self, frame: InputFrame) {
+        self.queue.clear();
+        self.current = frame;
+    }
+
+    pub fn next_frame(&mut self) -> &InputFrame {
+        if let Some(frame) = self.queue.pop_front() {
+            self.current = frame;
+        }
+        &self.current
+    }
+}
+
+#[derive(Resource, Default)]
+struct FixedTicks(u64);
+
+fn count_fixed_ticks(mut ticks: ResMut<FixedTicks>) {
+    ticks.0 += 1;
+}
+
+fn feed_scripted_input(
+    time: Res<Time>,
+    mut input_query: Query<(&mut ScriptedInput, &mut InputBuffer)>,
+) {
+    for (mut script, mut buffer) in &mut input_query {
+        buffer.tick(time.delta());
+        let frame = script.next_frame().clone();
+        buffer.apply_frame(&frame);
+    }
+}
+
+fn run_single_threaded(app: &mut App, label: impl ScheduleLabel) {
+    app.edit_schedule(label, |schedule| {
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    });
+}
+
+/// A headless app running phys64 on a manual clock, so every fixed tick is one `step` and runs
+/// are reproducible bit for bit.
+pub struct Simulation {
+    app: App,
+    character: Entity,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Simulation::new(Phys64Plugin::default())
+    }
+}
+
+impl Simulation {
+    pub fn new(plugin: Phys64Plugin) -> Self {
+        #[cfg(feature = "camera")]
+        let plugin = plugin.with_camera(false);
+        #[cfg(feature = "debug-inspector")]
+        let plugin = plugin.with_inspector(false);
+        let timestep = Duration::from_secs_f64(plugin.timestep);
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            HierarchyPlugin,
+            bevy::input::InputPlugin,
+            AssetPlugin::default(),
+            ScenePlugin,
+        ))
+        .init_asset::<Mesh>()
+        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+        .insert_resource(RapierConfiguration {
+            timestep_mode: TimestepMode::Fixed {
+                dt: timestep.as_secs_f32(),
+                substeps: 1,
+            },
+            ..default()
+        })
+        .add_plugins(plugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(timestep))
+        .init_resource::<FixedTicks>()
+        .add_systems(
+            FixedUpdate,
+            (
+                count_fixed_ticks,
+                feed_scripted_input
+                    .after(play_back_input)
+                    .before(record_input),
+            )
+                .chain()
+                .in_set(EngineSystemSet::Input),
+        );
+
+        // Any unordered pair of conflicting systems can swap between runs, refuse to start instead
+        app.edit_schedule(FixedUpdate, |schedule| {
+            schedule.set_build_settings(ScheduleBuildSettings {
+                ambiguity_detection: LogLevel::Error,
+                ..default()
+            });
+        });
+        run_single_threaded(&mut app, First);
+        run_single_threaded(&mut app, PreUpdate);
+        run_single_threaded(&mut app, FixedUpdate);
+        run_single_threaded(&mut app, Update);
+        run_single_threaded(&mut app, PostUpdate);
+        run_single_threaded(&mut app, Last);
+
+        let character = app
+            .world
+            .spawn((
+                TransformBundle::default(),
+                Player,
+                Collider::capsule_y(0.5, 0.5),
+                KinematicCharacterController {
+                    offset: CharacterLength::Absolute(0.01),
+                    snap_to_ground: Some(CharacterLength::Absolute(10.0)),
+                    autostep: Some(CharacterAutostep {
+                        max_height: CharacterLength::Absolute(0.5),
+                        min_width: CharacterLength::Absolute(0.25),
+                        include_dynamic_bodies: true,
+                    }),
+                    ..default()
+                },
+                MoveDirection::default(),
+                Momentum::default(),
+                Speed::default(),
+                Forces::default(),
+                GravityAffected,
+                GroundSensor::default(),
+                InputBuffer::default(),
+                Jumper::default(),
+                (
+                    Carrier::default(),
+                    WallSensor::default(),
+                    GroundPounder::default(),
+                    Diver::default(),
+                    LedgeGrabber::default(),
+                ),
+                ScriptedInput::default(),
+            ))
+            .id();
+
+        // The first update only starts the clocks, nothing is stepped yet
+        app.update();
+
+        Simulation { app, character }
+    }
+
+    /// A character settled on a wide flat floor, the starting point of most tests
+    pub fn on_flat_ground() -> Self {
+        let mut sim = Simulation::default();
+        sim.spawn_box(
+            Vec3::new(100.0, 0.25, 100.0),
+            Transform::from_xyz(0.0, -2.0, 0.0),
+        );
+        sim.place_character(Vec3::new(0.0, -0.75, 0.0));
+        // Touching down starts the triple jump chain, give it time to run out
+        sim.step(30);
+        sim
+    }
+
+    pub fn character(&self) -> Entity {
+        self.character
+    }
+
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    /// Spawns a fixed cuboid collider, e.g. a floor or a slope
+    pub fn spawn_box(&mut self, half_extents: Vec3, transform: Transform) -> Entity {
+        self.app
+            .world
+            .spawn((
+                TransformBundle::from_transform(transform),
+                Collider::cuboid(half_extents.x, half_extents.y, half_extents.z),
+                RigidBody::Fixed,
+            ))
+            .id()
+    }
+
+    pub fn place_character(&mut self, translation: Vec3) {
+        if let Some(mut transform) = self.app.world.get_mut::<Transform>(self.character) {
+            transform.translation = translation;
+        }
+    }
+
+    /// Queues frames to be played one per tick, after anything already queued
+    pub fn script(&mut self, frames: impl IntoIterator<Item = InputFrame>) {
+        let mut script = self.script_mut();
+        for frame in frames {
+            script.push(frame);
+        }
+    }
+
+    /// Queues every frame of a recorded replay
+    pub fn play(&mut self, replay: &Replay) {
+        self.script(replay.frames.iter().cloned());
+    }
+
+    /// Drops any queued frames and holds `frame` until told otherwise
+    pub fn hold(&mut self, frame: InputFrame) {
+        self.script_mut().hold(frame);
+    }
+
+    pub fn press(&mut self, action: PlayerAction) {
+        let mut script = self.script_mut();
+        let frame = script.current.clone().holding(action);
+        script.hold(frame);
+    }
+
+    pub fn release(&mut self, action: PlayerAction) {
+        let mut script = self.script_mut();
+        let mut frame = script.current.clone();
+        frame.pressed.retain(|held| *held != action);
+        script.hold(frame);
+    }
+
+    pub fn set_move_axis(&mut self, move_axis: Vec2) {
+        let mut script = self.script_mut();
+        let frame = script.current.clone().with_move_axis(move_axis);
+        script.hold(frame);
+    }
+
+    /// Runs exactly `ticks` iterations of `FixedUpdate`
+    pub fn step(&mut self, ticks: u32) {
+        let target = self.app.world.resource::<FixedTicks>().0 + ticks as u64;
+        while self.app.world.resource::<FixedTicks>().0 < target {
+            self.app.update();
+        }
+    }
+
+    /// Steps one tick at a time, calling `inspect` after each one
+    pub fn step_with(&mut self, ticks: u32, mut inspect: impl FnMut(&Simulation)) {
+        for _ in 0..ticks {
+            self.step(1);
+            inspect(self);
+        }
+    }
+
+    /// Steps one tick at a time until `done` holds after a tick, giving up after `max` ticks.
+    /// Returns how many ticks it took.
+    pub fn step_until(
+        &mut self,
+        max: u32,
+        mut done: impl FnMut(&Simulation) -> bool,
+    ) -> Option<u32> {
+        for ticks in 1..=max {
+            self.step(1);
+            if done(self) {
+                return Some(ticks);
+            }
+        }
+        None
+    }
+
+    pub fn transform(&self) -> Transform {
+        *self.component::<Transform>()
+    }
+
+    pub fn momentum(&self) -> Vec3 {
+        self.component::<Momentum>().get()
+    }
+
+    pub fn forces(&self) -> &Forces {
+        self.component::<Forces>()
+    }
+
+    pub fn ground_sensor(&self) -> &GroundSensor {
+        self.component::<GroundSensor>()
+    }
+
+    /// Events of type `E` sent during the most recent tick
+    pub fn events<E: Event + Clone>(&self) -> Vec<E> {
+        self.app
+            .world
+            .resource::<Events<E>>()
+            .iter_current_update_events()
+            .cloned()
+            .collect()
+    }
+
+    pub fn component<T: Component>(&self) -> &T {
+        self.app
+            .world
+            .get::<T>(self.character)
+            .expect("harness character is missing a component")
+    }
+
+    fn script_mut(&mut self) -> Mut<'_, ScriptedInput> {
+        self.app
+            .world
+            .get_mut::<ScriptedInput>(self.character)
+            .expect("harness character is missing ScriptedInput")
+    }
+}
@@ -1,18 +1,27 @@
-use crate::types::EngineSystemSet;
-use bevy::{prelude::*, utils::HashMap};
+use crate::{replay::InputPlayback, types::EngineSystemSet};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 use leafwing_input_manager::{prelude::*, *};
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
 
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(InputManagerPlugin::<PlayerAction>::default())
+        app.register_type::<InputBuffer>()
+            .register_type::<PlayerAction>()
+            .register_type::<HashSet<PlayerAction>>()
+            .register_type::<HashMap<PlayerAction, Timer>>()
+            .add_plugins(InputManagerPlugin::<PlayerAction>::default())
             .add_systems(FixedUpdate, buffer_inputs.in_set(EngineSystemSet::Input));
     }
 }
 
-#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Default, Reflect)]
+#[derive(
+    Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Default, Reflect, Serialize, Deserialize,
+)]
 pub enum PlayerAction {
     #[default]
     Jump,
@@ -25,39 +34,56 @@ pub enum PlayerAction {
     CamModeChangeNegative,
 }
 
-#[derive(Component, Default)]
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
 pub struct InputBuffer {
     pressed_actions: HashSet<PlayerAction>,
     stale_actions: HashSet<PlayerAction>,
     buffered_actions: HashMap<PlayerAction, Timer>,
+    move_axis: Vec2,
+}
+
+/// Everything held on the controller during a single fixed tick.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct InputFrame {
+    pub pressed: Vec<PlayerAction>,
+    pub move_axis: Vec2,
+}
+
+impl InputFrame {
+    pub fn new() -> Self {
+        InputFrame::default()
+    }
+
+    pub fn holding(mut self, action: PlayerAction) -> Self {
+        if !self.pressed.contains(&action) {
+            self.pressed.push(action);
+        }
+        self
+    }
+
+    pub fn with_move_axis(mut self, move_axis: Vec2) -> Self {
+        self.move_axis = move_axis;
+        self
+    }
 }
 
 #[allow(unused)]
 impl InputBuffer {
     pub fn just_pressed(&self, action: PlayerAction) -> bool {
-        match self.pressed_actions.get(&action) {
-            Some(_) => match self.stale_actions.get(&action) {
-                None => return true,
-                _ => (),
-            },
-            _ => (),
+        if self.pressed_actions.contains(&action) && !self.stale_actions.contains(&action) {
+            return true;
         }
 
-        match self.buffered_actions.get(&action) {
-            Some(_) => match self.stale_actions.get(&action) {
-                Some(_) => return false,
-                None => return true,
-            },
-            None => false,
-        }
+        self.buffered_actions.contains_key(&action) && !self.stale_actions.contains(&action)
     }
 
     pub fn pressed(&self, action: PlayerAction) -> bool {
-        self.pressed_actions.get(&action).is_some()
+        self.pressed_actions.contains(&action)
     }
 
     pub fn released(&self, action: PlayerAction) -> bool {
-        self.pressed_actions.get(&action).is_none() && self.buffered_actions.get(&action).is_none()
+        !self.pressed_actions.contains(&action) && !self.buffered_actions.contains_key(&action)
     }
 
     pub fn press(&mut self, action: PlayerAction) {
@@ -66,24 +92,55 @@ impl InputBuffer {
         self.pressed_actions.insert(action);
     }
 
+    /// Stops a press from counting as `just_pressed` again until it is released and re-pressed
+    pub fn consume(&mut self, action: PlayerAction) {
+        self.buffered_actions.remove(&action);
+        self.stale_actions.insert(action);
+    }
+
     pub fn release(&mut self, action: PlayerAction) {
         self.buffered_actions.remove(&action);
         self.stale_actions.remove(&action);
         self.pressed_actions.remove(&action);
     }
 
+    pub fn move_axis(&self) -> Vec2 {
+        self.move_axis
+    }
+
+    pub fn set_move_axis(&mut self, value: Vec2) {
+        self.move_axis = value;
+    }
+
+    /// Presses and releases whatever differs from `frame`, as if it came from the controller
+    pub fn apply_frame(&mut self, frame: &InputFrame) {
+        let released: Vec<PlayerAction> = self
+            .pressed_actions
+            .iter()
+            .filter(|action| !frame.pressed.contains(action))
+            .copied()
+            .collect();
+        for action in released {
+            self.release(action);
+        }
+
+        for action in frame.pressed.iter() {
+            if !self.pressed(*action) {
+                self.press(*action);
+            }
+        }
+        self.move_axis = frame.move_axis;
+    }
+
     pub fn tick(&mut self, delta: std::time::Duration) {
         let mut stale_buffers: Vec<PlayerAction> = Vec::new();
         self.buffered_actions
             .iter_mut()
             .for_each(|(action, timer)| {
                 timer.tick(delta);
-                match timer.finished() {
-                    true => {
-                        self.stale_actions.insert(*action);
-                        stale_buffers.push(*action);
-                    }
-                    _ => (),
+                if timer.finished() {
+                    self.stale_actions.insert(*action);
+                    stale_buffers.push(*action);
                 }
             });
         for action in stale_buffers.iter() {
@@ -131,9 +188,12 @@ impl InputListenerBundle {
     }
 }
 
-fn buffer_inputs(
+pub(crate) fn buffer_inputs(
     time: Res<Time>,
-    mut input_buffer_query: Query<(&mut InputBuffer, &ActionState<PlayerAction>)>,
+    mut input_buffer_query: Query<
+        (&mut InputBuffer, &ActionState<PlayerAction>),
+        Without<InputPlayback>,
+    >,
 ) {
     for (mut buffer, input) in &mut input_buffer_query {
         buffer.tick(time.delta());
@@ -147,5 +207,11 @@ fn buffer_inputs(
         for action in input.get_just_released().iter() {
             buffer.release(*action);
         }
+
+        let move_axis = input
+            .clamped_axis_pair(PlayerAction::Move)
+            .map(|axis| axis.xy())
+            .unwrap_or_default();
+        buffer.set_move_axis(move_axis);
     }
 }
@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+
+#[cfg(feature = "camera")]
+pub mod camera;
+#[cfg(feature = "harness")]
+pub mod harness;
+pub mod input;
+pub mod movement;
+pub mod player;
+pub mod replay;
+pub mod types;
+
+pub mod prelude {
+    #[cfg(feature = "camera")]
+    pub use crate::camera::{CameraPlugin, MainCamera};
+    pub use crate::{
+        input::{InputBuffer, InputFrame, InputListenerBundle, InputPlugin, PlayerAction},
+        movement::MovementPlugin,
+        player::{IndexPointer, Player, PlayerData, PlayerPlugin},
+        replay::{InputPlayback, InputRecorder, Replay},
+        types::*,
+        Phys64Plugin,
+    };
+}
+
+use types::{EngineSystemSet, Gravity, MomentumSystemSet};
+
+/// Wires up every phys64 subsystem, the `Gravity` resource, the fixed timestep and the
+/// `EngineSystemSet` ordering. Rapier is left to the app so it can be configured freely.
+pub struct Phys64Plugin {
+    gravity: f32,
+    terminal_velocity: f32,
+    timestep: f64,
+    #[cfg(feature = "sm64-moveset")]
+    moveset: bool,
+    #[cfg(feature = "camera")]
+    camera: bool,
+    #[cfg(feature = "debug-inspector")]
+    inspector: bool,
+}
+
+impl Default for Phys64Plugin {
+    fn default() -> Self {
+        Phys64Plugin {
+            gravity: 24.0,
+            terminal_velocity: 50.0,
+            timestep: 1.0 / 60.0,
+            #[cfg(feature = "sm64-moveset")]
+            moveset: true,
+            #[cfg(feature = "camera")]
+            camera: true,
+            #[cfg(feature = "debug-inspector")]
+            inspector: true,
+        }
+    }
+}
+
+impl Phys64Plugin {
+    /// Downward acceleration in units/s², 24 by default
+    pub fn with_gravity(mut self, amount: f32) -> Self {
+        self.gravity = amount;
+        self
+    }
+
+    pub fn with_terminal_velocity(mut self, speed: f32) -> Self {
+        self.terminal_velocity = speed;
+        self
+    }
+
+    pub fn with_timestep(mut self, seconds: f64) -> Self {
+        self.timestep = seconds;
+        self
+    }
+
+    #[cfg(feature = "sm64-moveset")]
+    pub fn with_moveset(mut self, enabled: bool) -> Self {
+        self.moveset = enabled;
+        self
+    }
+
+    #[cfg(feature = "camera")]
+    pub fn with_camera(mut self, enabled: bool) -> Self {
+        self.camera = enabled;
+        self
+    }
+
+    #[cfg(feature = "debug-inspector")]
+    pub fn with_inspector(mut self, enabled: bool) -> Self {
+        self.inspector = enabled;
+        self
+    }
+}
+
+impl Plugin for Phys64Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            movement::MovementPlugin,
+            input::InputPlugin,
+            player::PlayerPlugin,
+            replay::ReplayPlugin,
+        ))
+        .insert_resource(Gravity::new(self.gravity).with_terminal_velocity(self.terminal_velocity))
+        .insert_resource(Time::<Fixed>::from_seconds(self.timestep))
+        .configure_sets(
+            FixedUpdate,
+            (
+                EngineSystemSet::Input,
+                EngineSystemSet::CalculateMomentum,
+                EngineSystemSet::ApplyMomentum,
+            )
+                .chain(),
+        )
+        .configure_sets(
+            FixedUpdate,
+            (
+                MomentumSystemSet::Vertical,
+                MomentumSystemSet::Moveset,
+                MomentumSystemSet::Lateral,
+            )
+                .chain()
+                .in_set(EngineSystemSet::CalculateMomentum),
+        );
+
+        #[cfg(feature = "sm64-moveset")]
+        if self.moveset {
+            app.add_plugins(player::PlayerMovementPlugin);
+        }
+
+        #[cfg(feature = "camera")]
+        if self.camera {
+            app.add_plugins(camera::CameraPlugin);
+        }
+
+        #[cfg(feature = "debug-inspector")]
+        if self.inspector {
+            app.add_plugins(bevy_inspector_egui::quick::WorldInspectorPlugin::default());
+        }
+    }
+}
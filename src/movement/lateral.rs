@@ -5,10 +5,14 @@ pub struct LateralMovementPlugin;
 
 impl Plugin for LateralMovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            FixedUpdate,
-            (handle_speed, rotate_to_direction).in_set(EngineSystemSet::CalculateMomentum),
-        );
+        app.register_type::<Speed>()
+            .register_type::<MoveDirection>()
+            .add_systems(
+                FixedUpdate,
+                (handle_speed, rotate_to_direction)
+                    .chain()
+                    .in_set(MomentumSystemSet::Lateral),
+            );
     }
 }
 
@@ -26,10 +30,17 @@ fn handle_speed(
     >,
 ) {
     for (mut forces, mut speed, direction, transform, ground_sensor) in &mut query {
+        // No running up, or down, a slope too steep to stand on
+        if ground_sensor.on_steep_slope() {
+            forces.remove(ForceId::Run);
+            continue;
+        }
         if ground_sensor.grounded() {
+            let surface = ground_sensor.surface();
             if direction.is_active() {
-                speed.accelerate(time.delta(), time.delta_seconds());
-                let movement_force = speed.current() * transform.forward();
+                speed.accelerate(time.delta(), time.delta_seconds() * surface.acceleration);
+                let movement_force = ground_sensor
+                    .along_ground(speed.current() * surface.max_speed * transform.forward());
                 forces.add(
                     ForceId::Run,
                     Force::new(movement_force, None, ForceDecayType::Manual),
@@ -40,8 +51,12 @@ fn handle_speed(
                     forces.remove(ForceId::Run);
                     forces.add(
                         ForceId::Skid,
-                        Force::new(run_vec * 0.33, Some(0.25), ForceDecayType::Automatic),
-                    )
+                        Force::new(
+                            run_vec * 0.33 * surface.skid,
+                            Some(0.25 * surface.skid),
+                            ForceDecayType::Automatic,
+                        ),
+                    );
                 }
                 speed.tick_reset_timer(time.delta());
                 if speed.should_reset() {
@@ -54,11 +69,19 @@ fn handle_speed(
 
 fn rotate_to_direction(
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &MoveDirection, &Speed, &GroundSensor), Without<Sliding>>,
+    mut query: Query<(
+        &mut Transform,
+        &MoveDirection,
+        &Speed,
+        &GroundSensor,
+        Has<Sliding>,
+    )>,
     mut rotation_target: Local<Transform>,
 ) {
-    for (mut transform, direction, speed, ground_sensor) in &mut query {
-        if ground_sensor.grounded() {
+    for (mut transform, direction, speed, ground_sensor, is_sliding) in &mut query {
+        // A rotating platform turns whoever stands on it, sliding or not
+        transform.rotate_y(ground_sensor.platform_turn());
+        if ground_sensor.grounded() && !is_sliding {
             rotation_target.translation = transform.translation;
             let flat_velo_direction =
                 Vec3::new(direction.0.x, 0.0, direction.0.z).normalize_or_zero();
@@ -66,7 +89,7 @@ fn rotate_to_direction(
                 let target_position = rotation_target.translation + flat_velo_direction;
 
                 rotation_target.look_at(target_position, Vec3::Y);
-                let turn_speed = speed.current() * 0.5;
+                let turn_speed = speed.current() * 0.5 * ground_sensor.surface().turn_rate;
 
                 transform.rotation = transform
                     .rotation
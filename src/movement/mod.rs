@@ -1,24 +1,39 @@
 use crate::types::*;
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 use bevy_rapier3d::prelude::*;
 
 mod lateral;
 mod vertical;
+mod wind;
+
+#[cfg(feature = "sm64-moveset")]
+pub(crate) use vertical::find_ledge;
 
 pub struct MovementPlugin;
 
 impl Plugin for MovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((
-            lateral::LateralMovementPlugin,
-            vertical::VerticalMovementPlugin,
-        ))
-        .add_systems(
-            FixedUpdate,
-            (apply_forces, set_translation)
-                .chain()
-                .in_set(EngineSystemSet::ApplyMomentum),
-        );
+        app.register_type::<Momentum>()
+            .register_type::<Forces>()
+            .register_type::<HashMap<ForceId, Force>>()
+            .register_type::<Force>()
+            .register_type::<ForceId>()
+            .register_type::<CustomForceId>()
+            .register_type::<ForceDecayType>()
+            .register_type::<ForceFalloff>()
+            .register_type::<Option<Timer>>()
+            .add_plugins((
+                lateral::LateralMovementPlugin,
+                vertical::VerticalMovementPlugin,
+                wind::WindPlugin,
+            ))
+            .add_event::<ForceChanged>()
+            .add_systems(
+                FixedUpdate,
+                (apply_forces, announce_force_changes, set_translation)
+                    .chain()
+                    .in_set(EngineSystemSet::ApplyMomentum),
+            );
     }
 }
 fn apply_forces(time: Res<Time>, mut physics_query: Query<(&mut Momentum, &mut Forces)>) {
@@ -29,6 +44,24 @@ fn apply_forces(time: Res<Time>, mut physics_query: Query<(&mut Momentum, &mut F
     }
 }
 
+fn announce_force_changes(
+    mut forces_query: Query<(Entity, &mut Forces)>,
+    mut events: EventWriter<ForceChanged>,
+) {
+    for (entity, mut forces) in &mut forces_query {
+        events.send_batch(
+            forces
+                .drain_changes()
+                .map(|(force_id, vector, reason)| ForceChanged {
+                    entity,
+                    force_id,
+                    vector,
+                    reason,
+                }),
+        );
+    }
+}
+
 fn set_translation(mut query: Query<(&mut KinematicCharacterController, &Momentum)>) {
     for (mut character, momentum) in &mut query {
         let mut translation_to_apply: Vec3 = Vec3::ZERO;
@@ -1,24 +1,49 @@
-use crate::{
-    input::{InputBuffer, PlayerAction},
-    types::*,
-};
+use crate::types::*;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
+/// Largest `normal.y` a surface can have and still be the face of a ledge
+const LEDGE_WALL_NORMAL_Y: f32 = 0.3;
+/// Smallest `normal.y` the top of a ledge needs to be held on to
+const LEDGE_TOP_NORMAL_Y: f32 = 0.7;
+/// Height above the character's center that walls are looked for at
+const LEDGE_CHEST_HEIGHT: f32 = 0.5;
+/// Seconds a platform's velocity lasts after leaving it
+const PLATFORM_CARRY_TIME: f32 = 0.5;
+
 pub struct VerticalMovementPlugin;
 
 impl Plugin for VerticalMovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            FixedUpdate,
-            (
-                handle_jump_timer,
-                apply_gravity,
-                handle_ground_sensor,
-                stick_to_slopes,
-            )
-                .in_set(EngineSystemSet::CalculateMomentum),
-        );
+        app.register_type::<GroundSensor>()
+            .register_type::<SurfaceMaterial>()
+            .register_type::<SlopeTuning>()
+            .register_type::<TerminalVelocity>()
+            .register_type::<Jumper>()
+            .register_type::<JumpStage>()
+            .register_type::<JumpKind>()
+            .register_type::<Option<JumpKind>>()
+            .register_type::<LongJumpTuning>()
+            .register_type::<JumpProfile>()
+            .register_type::<LedgeGrabber>()
+            .register_type::<LedgeTuning>()
+            .register_type::<Ledge>()
+            .register_type::<Option<Ledge>>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    handle_ground_sensor,
+                    ride_platforms,
+                    handle_wall_sensor,
+                    // Before gravity, which mustn't build up on the tick a ledge is grabbed
+                    handle_ledge_sensor,
+                    handle_jump_timer,
+                    apply_gravity,
+                    stick_to_slopes,
+                )
+                    .chain()
+                    .in_set(MomentumSystemSet::Vertical),
+            );
     }
 }
 
@@ -31,18 +56,35 @@ fn handle_jump_timer(time: Res<Time>, mut jump_query: Query<(&mut Jumper, &Groun
 }
 
 fn apply_gravity(
+    time: Res<Time>,
     gravity: Res<Gravity>,
-    mut character_query: Query<(&mut Forces, &GroundSensor), With<GravityAffected>>,
+    mut character_query: Query<
+        (&mut Forces, &GroundSensor, Option<&TerminalVelocity>),
+        With<GravityAffected>,
+    >,
 ) {
-    for (mut forces, ground_sensor) in &mut character_query {
+    for (mut forces, ground_sensor, terminal_velocity) in &mut character_query {
+        let terminal_velocity = terminal_velocity
+            .and_then(|terminal_velocity| terminal_velocity.0)
+            .or(gravity.terminal_velocity());
         if !ground_sensor.grounded() {
             if !forces.has_key(ForceId::Gravity) {
-                forces.add(
-                    ForceId::Gravity,
-                    Force::new(gravity.force(), None, ForceDecayType::Manual),
+                // Start half a tick in so each tick moves by its average fall speed, that keeps
+                // jump arcs the same at any tick rate
+                let force = Force::new(
+                    gravity.force() * time.delta_seconds() * 0.5,
+                    None,
+                    ForceDecayType::Manual,
                 );
+                let force = match terminal_velocity {
+                    Some(speed) => force.with_max_magnitude(speed),
+                    None => force,
+                };
+                forces.add(ForceId::Gravity, force);
             } else {
-                forces.add_to(ForceId::Gravity, gravity.force());
+                // The terminal velocity may have changed mid-fall, or someone else added gravity
+                forces.set_max_magnitude(ForceId::Gravity, terminal_velocity);
+                forces.add_to(ForceId::Gravity, gravity.force() * time.delta_seconds());
             }
         } else if forces.has_key(ForceId::Gravity) {
             forces.remove(ForceId::Gravity);
@@ -50,28 +92,56 @@ fn apply_gravity(
     }
 }
 
-fn stick_to_slopes(mut character_query: Query<(&mut Forces, &GroundSensor)>) {
-    for (mut forces, sensor) in &mut character_query {
+fn stick_to_slopes(
+    time: Res<Time>,
+    gravity: Res<Gravity>,
+    mut character_query: Query<(&mut Forces, &GroundSensor, Has<Sliding>)>,
+) {
+    for (mut forces, sensor, is_sliding) in &mut character_query {
+        // A slide already follows the slope on its own
+        if sensor.on_steep_slope() && !is_sliding {
+            // Too steep to stand on, slide down it and keep picking up speed
+            let downhill = sensor.get_slope_gradient().normalize_or_zero();
+            let speed = forces
+                .get_vector(ForceId::Slope)
+                .map_or(0.0, |slope| slope.dot(downhill).max(0.0));
+            let speed = speed + sensor.slopes().slide_acceleration * time.delta_seconds();
+            forces.add(
+                ForceId::Slope,
+                Force::new(downhill * speed, None, ForceDecayType::Manual),
+            );
+            continue;
+        }
+
+        // Back on walkable ground, the slide off the steep part stops here
+        let slid_off = forces
+            .get_vector(ForceId::Slope)
+            .is_some_and(|slope| slope.x != 0.0 || slope.z != 0.0);
+        if sensor.grounded() && slid_off {
+            forces.remove(ForceId::Slope);
+        }
+
         if sensor.grounded() && sensor.get_surface_angle() > 5.0 {
-            if !forces.has_key(ForceId::Slope) {
-                forces.add(
-                    ForceId::Slope,
-                    Force::new(
-                        Vec3::NEG_Y * sensor.get_surface_angle() * sensor.get_surface_angle(),
-                        None,
-                        ForceDecayType::Manual,
-                    ),
-                );
-            }
-        } else if !sensor.grounded() {
-            if forces.has_key(ForceId::Slope) {
-                forces.remove(ForceId::Slope);
-            }
+            // Close whatever gap the probe found within this tick, then press on as hard as a tick
+            // of gravity would, grounded characters have none of their own
+            let gap = sensor.ground_hit().map_or(0.0, |hit| hit.distance.max(0.0));
+            forces.add(
+                ForceId::Slope,
+                Force::new(
+                    Vec3::NEG_Y * gap / time.delta_seconds()
+                        + gravity.force() * time.delta_seconds(),
+                    None,
+                    ForceDecayType::Manual,
+                ),
+            );
+        } else if !sensor.grounded() && forces.has_key(ForceId::Slope) {
+            forces.remove(ForceId::Slope);
         }
     }
 }
 
 fn handle_ground_sensor(
+    time: Res<Time>,
     mut ground_sensor_query: Query<(
         Entity,
         &mut GroundSensor,
@@ -80,6 +150,7 @@ fn handle_ground_sensor(
         &Momentum,
         &Transform,
     )>,
+    surface_query: Query<&SurfaceMaterial>,
     rapier_context: Res<RapierContext>,
 ) {
     for (entity, mut ground_sensor, mut forces, mut jumper, momentum, transform) in
@@ -90,37 +161,299 @@ fn handle_ground_sensor(
         let shape_rotation = transform.rotation;
         let cast_direction = Vec3::NEG_Y;
         let cast_shape = ground_sensor.shape_ref();
-        let cast_distance = 0.3;
+        let cast_distance = GROUND_CONTACT_DISTANCE;
         let stop_at_penetration = false;
         let cast_filter = QueryFilter::new().exclude_collider(entity);
 
-        if let Some(_) = rapier_context.cast_shape(
+        // Look well past the feet, only a hit within `cast_distance` counts as standing on it
+        let probe = rapier_context.cast_shape(
             cast_origin,
             shape_rotation,
             cast_direction,
             cast_shape,
-            cast_distance,
+            ground_sensor.probe_distance(),
             stop_at_penetration,
             cast_filter,
-        ) {
-            if momentum.y() <= 0.0 && !ground_sensor.grounded() {
+        );
+        ground_sensor.set_ground_hit(probe.map(|(ground, toi)| {
+            let center = cast_origin + cast_direction * toi.toi;
+            let (point, normal) = match toi.details {
+                Some(details) => (
+                    center + shape_rotation * details.witness2,
+                    -(shape_rotation * details.normal2),
+                ),
+                None => (center, Vec3::Y),
+            };
+            GroundHit {
+                entity: ground,
+                point,
+                normal,
+                distance: toi.toi,
+            }
+        }));
+        let hit = probe.filter(|(_, toi)| toi.toi <= cast_distance);
+        // The slope under the middle of the shape, or under wherever it touches down when that
+        // misses, e.g. an edge of it on a steep slope or the crest of a ramp. The shape cast's own
+        // normal at a crest is the edge's, which would flatten the run-up.
+        let normal = rapier_context
+            .cast_ray_and_get_normal(
+                cast_origin,
+                cast_direction,
+                cast_distance,
+                true,
+                cast_filter,
+            )
+            .or_else(|| {
+                let contact = ground_sensor.ground_hit().filter(|_| hit.is_some())?;
+                rapier_context.cast_ray_and_get_normal(
+                    contact.point + Vec3::Y * 0.05,
+                    Vec3::NEG_Y,
+                    0.1,
+                    true,
+                    cast_filter,
+                )
+            });
+        if let Some((_, intersection)) = normal {
+            ground_sensor.set_normal(intersection.normal);
+        }
+        // Measured against the ground rather than straight up, so running uphill isn't a jump
+        let leaving_ground = momentum.get().dot(ground_sensor.get_normal()) > 1e-4;
+        if let Some((ground, _)) = hit {
+            let surface = surface_query.get(ground).copied().unwrap_or_default();
+            ground_sensor.set_surface(surface);
+        }
+        if hit.is_some() {
+            if !leaving_ground && !ground_sensor.grounded() {
                 ground_sensor.set_state(GroundedState::Grounded);
                 forces.remove(ForceId::Jump);
                 forces.remove(ForceId::Slide);
+                forces.remove(ForceId::LongJump);
+                forces.remove(ForceId::WallKick);
+                forces.remove(ForceId::Flip);
                 jumper.land();
             }
+        } else if ground_sensor.grounded() && !leaving_ground {
+            // Walked off an edge rather than jumped, give a late jump a moment to still count
+            ground_sensor.set_state(GroundedState::Coyote);
+        } else if ground_sensor.in_coyote_time() && !leaving_ground {
+            ground_sensor.tick_coyote_time(time.delta());
         } else {
             ground_sensor.set_state(GroundedState::Airborne);
         }
-        //Cast a ray to get the angle of our slope
-        if let Some((_, intersection)) = rapier_context.cast_ray_and_get_normal(
-            cast_origin,
+        ground_sensor.tick_state_time(time.delta());
+    }
+}
+
+/// Carries grounded characters along with the body under them. Jumping or falling off keeps the
+/// platform's velocity for a moment, fading out over `PLATFORM_CARRY_TIME`.
+fn ride_platforms(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut character_query: Query<(&mut Forces, &Transform, &mut GroundSensor)>,
+) {
+    let delta = time.delta_seconds();
+    for (mut forces, transform, mut sensor) in &mut character_query {
+        sensor.set_platform_turn(0.0);
+        if delta <= 0.0 {
+            continue;
+        }
+        if !sensor.grounded() {
+            if sensor.riding_platform() {
+                sensor.set_riding_platform(false);
+                if let Some(velocity) = forces.get_vector(ForceId::Platform) {
+                    forces.add(
+                        ForceId::Platform,
+                        Force::new(
+                            velocity,
+                            Some(PLATFORM_CARRY_TIME),
+                            ForceDecayType::Automatic,
+                        )
+                        .with_falloff(ForceFalloff::Linear),
+                    );
+                }
+            }
+            continue;
+        }
+        let platform = sensor
+            .ground_entity()
+            .and_then(|ground| rapier_context.collider_parent(ground))
+            .and_then(|body| rapier_context.entity2body().get(&body))
+            .and_then(|handle| rapier_context.bodies.get(*handle))
+            .filter(|body| !body.is_fixed());
+        let linvel: Vec3 = platform.map_or(Vec3::ZERO, |body| (*body.linvel()).into());
+        let angvel: Vec3 = platform.map_or(Vec3::ZERO, |body| (*body.angvel()).into());
+        // Standing still on a platform that isn't moving is just standing on the ground
+        let Some(platform) = platform.filter(|_| linvel != Vec3::ZERO || angvel != Vec3::ZERO)
+        else {
+            sensor.set_riding_platform(false);
+            forces.remove(ForceId::Platform);
+            continue;
+        };
+
+        let center: Vec3 = (*platform.center_of_mass()).into();
+        // Follow the arc exactly rather than its tangent, which would fling the character outwards
+        let offset = transform.translation - center;
+        let turned = Quat::from_scaled_axis(angvel * delta) * offset;
+        let velocity = linvel + (turned - offset) / delta;
+        forces.add(
+            ForceId::Platform,
+            Force::new(velocity, None, ForceDecayType::Manual),
+        );
+        // Facing is left to the lateral systems, which turn the character by this
+        sensor.set_riding_platform(true);
+        sensor.set_platform_turn(angvel.y * delta);
+    }
+}
+
+fn handle_wall_sensor(
+    time: Res<Time>,
+    mut wall_sensor_query: Query<(Entity, &mut WallSensor, &GroundSensor, &Forces, &Transform)>,
+    rapier_context: Res<RapierContext>,
+) {
+    for (entity, mut wall_sensor, ground_sensor, forces, transform) in &mut wall_sensor_query {
+        if ground_sensor.grounded() {
+            wall_sensor.land();
+            continue;
+        }
+        wall_sensor.tick(time.delta());
+
+        // Only look where the character is heading, brushing past a wall doesn't count
+        let heading = forces.get_combined_force();
+        let cast_direction = Vec3::new(heading.x, 0.0, heading.z).normalize_or_zero();
+        if cast_direction == Vec3::ZERO {
+            continue;
+        }
+        let cast_distance = 0.7;
+        let cast_filter = QueryFilter::new().exclude_collider(entity);
+
+        if let Some((wall, intersection)) = rapier_context.cast_ray_and_get_normal(
+            transform.translation,
             cast_direction,
             cast_distance,
             true,
             cast_filter,
         ) {
-            ground_sensor.set_normal(intersection.normal);
+            if intersection.normal.y.abs() <= wall_sensor.tuning().max_normal_y {
+                wall_sensor.touch(WallContact {
+                    wall,
+                    normal: intersection.normal,
+                });
+            }
+        }
+    }
+}
+
+/// Grabs ledges while falling past them and keeps hanging characters on theirs
+fn handle_ledge_sensor(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut ledge_query: Query<(
+        Entity,
+        &mut LedgeGrabber,
+        &mut Forces,
+        &mut Transform,
+        &GroundSensor,
+        &Momentum,
+        Option<&Carrier>,
+    )>,
+) {
+    for (entity, mut grabber, mut forces, mut transform, ground_sensor, momentum, carrier) in
+        &mut ledge_query
+    {
+        grabber.tick(time.delta());
+        let tuning = *grabber.tuning();
+        let mut filter = QueryFilter::new().exclude_collider(entity);
+        // A carried object sits right in front of the chest, don't mistake it for a wall
+        if let Some(held) = carrier.and_then(Carrier::held) {
+            filter = filter.exclude_rigid_body(held);
+        }
+
+        if let Some(ledge) = grabber.ledge() {
+            // Shimmying moves the character along the wall, follow the edge from wherever it is now
+            let found = find_ledge(
+                &rapier_context,
+                transform.translation,
+                -ledge.normal,
+                &tuning,
+                filter,
+            );
+            // Touching down or getting knocked back takes the character off the ledge too
+            let knocked_off = ground_sensor.grounded() || forces.has_key(ForceId::Knockback);
+            match found.filter(|_| !knocked_off) {
+                Some(ledge) => {
+                    grabber.grab(ledge);
+                    transform.translation = ledge.hang_position(&tuning);
+                }
+                None => {
+                    forces.remove_now(ForceId::Hang);
+                    grabber.let_go();
+                }
+            }
+            continue;
+        }
+
+        if ground_sensor.grounded() || momentum.y() > 0.0 || !grabber.can_grab() {
+            continue;
+        }
+        let facing = transform.forward();
+        let facing = Vec3::new(facing.x, 0.0, facing.z).normalize_or_zero();
+        if facing == Vec3::ZERO {
+            continue;
         }
+        let Some(ledge) = find_ledge(
+            &rapier_context,
+            transform.translation,
+            facing,
+            &tuning,
+            filter,
+        ) else {
+            continue;
+        };
+
+        forces.add(
+            ForceId::Hang,
+            Force::new(Vec3::ZERO, None, ForceDecayType::Manual),
+        );
+        grabber.grab(ledge);
+        transform.translation = ledge.hang_position(&tuning);
+        let target = transform.translation - ledge.normal;
+        transform.look_at(target, Vec3::Y);
+    }
+}
+
+/// Looks for a wall in front of a character at `origin` whose top is within its grab height
+pub(crate) fn find_ledge(
+    rapier_context: &RapierContext,
+    origin: Vec3,
+    facing: Vec3,
+    tuning: &LedgeTuning,
+    filter: QueryFilter,
+) -> Option<Ledge> {
+    let chest = origin + Vec3::Y * LEDGE_CHEST_HEIGHT;
+    let (wall, wall_hit) =
+        rapier_context.cast_ray_and_get_normal(chest, facing, tuning.reach, true, filter)?;
+    let normal = Vec3::new(wall_hit.normal.x, 0.0, wall_hit.normal.z).normalize_or_zero();
+    if wall_hit.normal.y.abs() > LEDGE_WALL_NORMAL_Y || normal == Vec3::ZERO {
+        return None;
+    }
+
+    // Look down onto the wall from just behind its face. A ray starting inside the wall means the
+    // top is out of reach.
+    let above = wall_hit.point - normal * 0.1 + Vec3::Y * tuning.grab_height;
+    let (_, top_hit) = rapier_context.cast_ray_and_get_normal(
+        above,
+        Vec3::NEG_Y,
+        tuning.grab_height,
+        true,
+        filter,
+    )?;
+    if top_hit.toi <= 0.0 || top_hit.normal.y < LEDGE_TOP_NORMAL_Y {
+        return None;
     }
+
+    Some(Ledge {
+        wall,
+        point: Vec3::new(wall_hit.point.x, top_hit.point.y, wall_hit.point.z),
+        normal,
+    })
 }
@@ -0,0 +1,71 @@
+use crate::types::*;
+use bevy::{prelude::*, utils::HashSet};
+use bevy_rapier3d::prelude::*;
+
+pub struct WindPlugin;
+
+impl Plugin for WindPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<WindZone>()
+            .register_type::<WindFalloff>()
+            .add_systems(
+                FixedUpdate,
+                apply_wind
+                    .in_set(EngineSystemSet::CalculateMomentum)
+                    .after(MomentumSystemSet::Lateral),
+            );
+    }
+}
+
+fn apply_wind(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    zone_query: Query<(Entity, &WindZone, &Collider, &GlobalTransform)>,
+    mut forces_query: Query<(&mut Forces, &GlobalTransform, Option<&GroundSensor>)>,
+    mut blown: Local<HashSet<Entity>>,
+) {
+    let elapsed = time.elapsed_seconds();
+    let mut winds: Vec<(Entity, Vec3)> = Vec::new();
+    for (zone_entity, zone, collider, zone_transform) in &zone_query {
+        let (scale, rotation, origin) = zone_transform.to_scale_rotation_translation();
+        let filter = QueryFilter::new()
+            .exclude_sensors()
+            .exclude_collider(zone_entity);
+        rapier_context.intersections_with_shape(origin, rotation, collider, filter, |entity| {
+            if let Ok((_, transform, sensor)) = forces_query.get(entity) {
+                let offset = rotation.inverse() * (transform.translation() - origin) / scale;
+                let airborne = sensor.is_some_and(|sensor| !sensor.grounded());
+                winds.push((entity, rotation * zone.force_at(offset, elapsed, airborne)));
+            }
+            true
+        });
+    }
+
+    // Group by entity, the query pipeline doesn't promise an order
+    winds.sort_by_key(|(entity, _)| *entity);
+    let mut totals: Vec<(Entity, Vec3)> = Vec::new();
+    for (entity, wind) in winds {
+        match totals.last_mut() {
+            Some((last, total)) if *last == entity => *total += wind,
+            _ => totals.push((entity, wind)),
+        }
+    }
+
+    let mut inside = HashSet::new();
+    for (entity, total) in totals {
+        inside.insert(entity);
+        if let Ok((mut forces, ..)) = forces_query.get_mut(entity) {
+            forces.add(
+                ForceId::Wind,
+                Force::new(total, None, ForceDecayType::Manual),
+            );
+        }
+    }
+
+    for entity in blown.difference(&inside) {
+        if let Ok((mut forces, ..)) = forces_query.get_mut(*entity) {
+            forces.remove(ForceId::Wind);
+        }
+    }
+    *blown = inside;
+}
@@ -1 +1,119 @@
+use super::Player;
+use crate::{
+    input::{InputBuffer, PlayerAction},
+    types::*,
+};
 use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+/// How far off the facing direction, as a dot product, an object can be and still get picked up
+const PICKUP_CONE: f32 = 0.5;
+
+pub(super) fn pick_up_or_throw(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut carrier_query: Query<
+        (
+            Entity,
+            &mut Carrier,
+            &mut InputBuffer,
+            &Transform,
+            &Momentum,
+            &mut Speed,
+            &mut Jumper,
+            Option<&mut KinematicCharacterController>,
+        ),
+        (With<Player>, Without<Sliding>),
+    >,
+    carryable_query: Query<
+        (Entity, &Transform, &Carryable, Option<&CollisionGroups>),
+        Without<Held>,
+    >,
+    held_query: Query<(&Carryable, &Held)>,
+) {
+    for (entity, mut carrier, mut buffer, transform, momentum, mut speed, mut jumper, controller) in
+        &mut carrier_query
+    {
+        if !buffer.just_pressed(PlayerAction::Interact) {
+            continue;
+        }
+
+        if let Some(held) = carrier.held() {
+            // The buffered press stays live for a few ticks, only one throw or pickup per press
+            buffer.consume(PlayerAction::Interact);
+            if let Ok((carryable, held_by)) = held_query.get(held) {
+                // Momentum is the carrier's movement over the last tick, turn it back into a velocity
+                let carried_velocity = if time.delta_seconds() > 0.0 {
+                    momentum.get() / time.delta_seconds()
+                } else {
+                    Vec3::ZERO
+                };
+                let velocity =
+                    transform.forward() * carrier.throw_speed * carryable.weight.throw_scale()
+                        + Vec3::Y * carrier.throw_lift
+                        + carried_velocity;
+                let mut object = commands.entity(held);
+                object
+                    .remove::<Held>()
+                    .insert((RigidBody::Dynamic, Velocity::linear(velocity)));
+                match held_by.collision_groups {
+                    Some(groups) => object.insert(groups),
+                    None => object.remove::<CollisionGroups>(),
+                };
+            }
+            if let Some(mut controller) = controller {
+                carrier.restore_filter(&mut controller);
+            }
+            carrier.set_held(None);
+            speed.set_max_scale(1.0);
+            jumper.set_force_scale(1.0);
+            continue;
+        }
+
+        let forward = transform.forward();
+        let closest = carryable_query
+            .iter()
+            .filter(|(object, ..)| *object != entity)
+            .filter_map(|(object, object_transform, carryable, groups)| {
+                let to_object = object_transform.translation - transform.translation;
+                let distance = to_object.length();
+                let in_front = to_object.normalize_or_zero().dot(forward) > PICKUP_CONE;
+                (in_front && distance <= carrier.reach)
+                    .then_some((object, distance, carryable, groups))
+            })
+            .min_by(|(_, a, ..), (_, b, ..)| a.total_cmp(b));
+
+        if let Some((object, _, carryable, groups)) = closest {
+            buffer.consume(PlayerAction::Interact);
+            // Still solid to everything else, it's only the carrier walking into it that's a problem
+            commands.entity(object).insert((
+                Held {
+                    carrier: entity,
+                    collision_groups: groups.copied(),
+                },
+                CollisionGroups::new(carrier.held_group, Group::ALL),
+                RigidBody::KinematicPositionBased,
+            ));
+            if let Some(mut controller) = controller {
+                carrier.filter_out_held(&mut controller);
+            }
+            carrier.set_held(Some(object));
+            speed.set_max_scale(carryable.weight.speed_scale());
+            jumper.set_force_scale(carryable.weight.jump_scale());
+        }
+    }
+}
+
+/// Keeps held objects pinned to their carrier
+pub(super) fn carry_held_objects(
+    carrier_query: Query<&Transform, (With<Carrier>, Without<Held>)>,
+    mut held_query: Query<(&mut Transform, &Carryable, &Held)>,
+) {
+    for (mut transform, carryable, held) in &mut held_query {
+        if let Ok(carrier_transform) = carrier_query.get(held.carrier) {
+            transform.translation =
+                carrier_transform.translation + carrier_transform.rotation * carryable.offset;
+            transform.rotation = carrier_transform.rotation;
+        }
+    }
+}
@@ -1,8 +1,14 @@
+use crate::types::{Carrier, Forces, GroundSensor, Held, WallSensor};
 use bevy::prelude::*;
 
+#[cfg(feature = "sm64-moveset")]
 mod grabbing;
+#[cfg(feature = "sm64-moveset")]
 mod movement;
 
+#[cfg(feature = "sm64-moveset")]
+pub use movement::PlayerMovementPlugin;
+
 #[derive(Component)]
 pub struct Player;
 
@@ -17,13 +23,17 @@ pub struct PlayerData {
     pub defacto_speed: f32,
     pub kicked_wall: Option<Entity>,
     pub jump_stage: u8,
+    /// Set while the combined force is held back by `Forces::with_max_combined`
+    pub speed_clamped: bool,
 }
 
-#[derive(Default)]
+/// Which entity the player is carrying, by `Entity::index`
+#[derive(Default, PartialEq, Eq, Debug)]
 pub enum IndexPointer {
     #[default]
     Empty,
     FindAt(usize),
+    /// Picked up, but not marked `Held` yet
     WaitFor(usize),
 }
 
@@ -31,16 +41,47 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(movement::PlayerMovementPlugin)
+        app.init_resource::<PlayerData>()
             .add_systems(Update, update_player_data);
     }
 }
 
 fn update_player_data(
     mut player_data: ResMut<PlayerData>,
-    player_query: Query<&Transform, With<Player>>,
+    player_query: Query<
+        (
+            &Transform,
+            Option<&GroundSensor>,
+            Option<&Forces>,
+            Option<&Carrier>,
+            Option<&WallSensor>,
+        ),
+        With<Player>,
+    >,
+    held_query: Query<&Transform, With<Held>>,
 ) {
-    for transform in &player_query {
+    for (transform, ground_sensor, forces, carrier, wall_sensor) in &player_query {
         player_data.player_position = transform.translation;
+        if let Some(ground_sensor) = ground_sensor {
+            let hit = ground_sensor.ground_hit();
+            // Nothing within the probe, as far as shadows and landing go the floor is out of reach
+            player_data.distance_from_floor = hit.map_or(f32::INFINITY, |hit| hit.distance);
+            player_data.floor_normal = hit.map_or(Vec3::Y, |hit| hit.normal);
+        }
+        player_data.speed_clamped = forces.is_some_and(Forces::is_clamped);
+        player_data.kicked_wall = wall_sensor.and_then(WallSensor::kicked_wall);
+        player_data.held_object_index = match carrier.and_then(Carrier::held) {
+            Some(held) => match held_query.get(held) {
+                Ok(held_transform) => {
+                    player_data.held_object_position = held_transform.translation;
+                    IndexPointer::FindAt(held.index() as usize)
+                }
+                Err(_) => IndexPointer::WaitFor(held.index() as usize),
+            },
+            None => {
+                player_data.held_object_position = Vec3::ZERO;
+                IndexPointer::Empty
+            }
+        };
     }
 }
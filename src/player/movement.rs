@@ -1,33 +1,54 @@
-use super::Player;
+use super::{grabbing, Player};
 use crate::{
     camera::MainCamera,
     input::{InputBuffer, PlayerAction},
+    movement::find_ledge,
     types::*,
 };
 use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
 
 pub struct PlayerMovementPlugin;
 
 impl Plugin for PlayerMovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            FixedUpdate,
-            (
-                set_player_direction,
-                apply_drift,
-                jump,
-                release_jump,
-                enter_sliding,
+        app.add_event::<GroundPoundLanded>()
+            .add_event::<SlideStarted>()
+            .add_event::<SlideEnded>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    set_player_direction,
+                    // Hanging from a ledge takes every press before any other move can see it
+                    hang,
+                    apply_drift,
+                    // Judging the release against the momentum of the tick a jump starts cuts it short
+                    release_jump,
+                    // Interact picks things up when it can and dives otherwise
+                    grabbing::pick_up_or_throw,
+                    // Rolling out of a belly slide takes the Jump press before it can start a jump
+                    dive,
+                    jump,
+                    wall_kick,
+                    enter_sliding,
+                    // Consumes Crouch presses made on the ground, so it has to come after sliding
+                    ground_pound,
+                    handle_sliding_momentum,
+                    // Slides start and end through commands, apply them so events go out this tick
+                    apply_deferred,
+                    announce_slides,
+                )
+                    .chain()
+                    .in_set(MomentumSystemSet::Moveset),
             )
-                .in_set(EngineSystemSet::CalculateMomentum),
-        );
+            .add_systems(
+                FixedUpdate,
+                grabbing::carry_held_objects.in_set(EngineSystemSet::ApplyMomentum),
+            );
     }
 }
 
-fn get_direction_in_camera_space(
-    camera_transform: &Transform,
-    input: &Res<Input<KeyCode>>,
-) -> Vec3 {
+fn get_direction_in_camera_space(camera_transform: &Transform, move_axis: Vec2) -> Vec3 {
     let mut forward = camera_transform.forward();
     forward.y = 0.0;
     forward = forward.normalize();
@@ -36,52 +57,101 @@ fn get_direction_in_camera_space(
     right.y = 0.0;
     right = right.normalize();
 
-    let mut x = 0.0;
-    let mut y = 0.0;
-
-    if input.pressed(KeyCode::A) {
-        x -= 1.0;
-    }
-    if input.pressed(KeyCode::D) {
-        x += 1.0;
-    }
-    if input.pressed(KeyCode::W) {
-        y += 1.0;
-    }
-    if input.pressed(KeyCode::S) {
-        y -= 1.0;
-    }
-
-    let right_vec: Vec3 = x * right;
-    let forward_vec: Vec3 = y * forward;
+    let right_vec: Vec3 = move_axis.x * right;
+    let forward_vec: Vec3 = move_axis.y * forward;
 
     right_vec + forward_vec
 }
 
 fn set_player_direction(
-    mut player_query: Query<&mut MoveDirection, With<Player>>,
+    mut player_query: Query<(&mut MoveDirection, &InputBuffer), With<Player>>,
     camera_query: Query<&Transform, With<MainCamera>>,
-    input: Res<Input<KeyCode>>,
 ) {
-    let camera_transform = camera_query.single();
-    for mut direction in &mut player_query {
-        direction.0 = get_direction_in_camera_space(camera_transform, &input);
+    let camera_transform = camera_query.get_single().copied().unwrap_or_default();
+    for (mut direction, buffer) in &mut player_query {
+        direction.0 = get_direction_in_camera_space(&camera_transform, buffer.move_axis());
+    }
+}
+
+fn hang(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut query: Query<
+        (
+            Entity,
+            &mut LedgeGrabber,
+            &mut Forces,
+            &mut InputBuffer,
+            &mut Transform,
+            &MoveDirection,
+        ),
+        With<Player>,
+    >,
+) {
+    for (entity, mut grabber, mut forces, mut buffer, mut transform, direction) in &mut query {
+        let Some(ledge) = grabber.ledge() else {
+            continue;
+        };
+        let tuning = *grabber.tuning();
+        let climb = buffer.just_pressed(PlayerAction::Jump);
+        let drop = buffer.just_pressed(PlayerAction::Crouch);
+        for action in [
+            PlayerAction::Jump,
+            PlayerAction::Crouch,
+            PlayerAction::Interact,
+        ] {
+            if buffer.just_pressed(action) {
+                buffer.consume(action);
+            }
+        }
+
+        if climb || drop {
+            forces.remove_now(ForceId::Hang);
+            grabber.let_go();
+            if climb {
+                transform.translation = ledge.climb_position(&tuning);
+            }
+            continue;
+        }
+
+        let tangent = ledge.tangent();
+        let shimmy = tangent * direction.0.dot(tangent) * tuning.shimmy_speed;
+        // Stop at the end of the ledge rather than shimmying off it
+        let next = transform.translation + shimmy * time.delta_seconds();
+        let filter = QueryFilter::new().exclude_collider(entity);
+        let shimmy = match find_ledge(&rapier_context, next, -ledge.normal, &tuning, filter) {
+            Some(_) => shimmy,
+            None => Vec3::ZERO,
+        };
+        // Re-adding an unchanged Hang would only churn the forces it holds off
+        if forces.get_vector(ForceId::Hang) != Some(shimmy) {
+            forces.add(
+                ForceId::Hang,
+                Force::new(shimmy, None, ForceDecayType::Manual),
+            );
+        }
     }
 }
 
 fn apply_drift(
     time: Res<Time>,
-    mut character_query: Query<(&mut Forces, &GroundSensor), With<Player>>,
+    mut character_query: Query<
+        (&mut Forces, &GroundSensor, &InputBuffer, Option<&Jumper>),
+        With<Player>,
+    >,
     camera_query: Query<&Transform, With<MainCamera>>,
-    input: Res<Input<KeyCode>>,
 ) {
-    let camera_transform = camera_query.single();
-    for (mut forces, ground_sensor) in &mut character_query {
+    let camera_transform = camera_query.get_single().copied().unwrap_or_default();
+    for (mut forces, ground_sensor, buffer, jumper) in &mut character_query {
         if ground_sensor.grounded() {
             forces.remove(ForceId::Drift);
         } else {
-            let drift = get_direction_in_camera_space(camera_transform, &input);
-            forces.add_to(ForceId::Drift, drift * time.delta_seconds() * 5.0);
+            let control = match jumper {
+                Some(jumper) if jumper.is_long_jumping() => jumper.long_jump_tuning().drift_scale,
+                _ => 1.0,
+            };
+            let drift = get_direction_in_camera_space(&camera_transform, buffer.move_axis());
+            forces.add_to(ForceId::Drift, drift * time.delta_seconds() * 5.0 * control);
         }
     }
 }
@@ -91,15 +161,14 @@ fn enter_sliding(
     mut player_query: Query<(Entity, &InputBuffer, &GroundSensor, &mut Forces), Without<Sliding>>,
 ) {
     for (entity, buffer, ground_sensor, mut forces) in &mut player_query {
-        if buffer.pressed(PlayerAction::Crouch)
+        // Only a fresh press, holding Crouch would restart the slide as soon as it wears off
+        if buffer.just_pressed(PlayerAction::Crouch)
             && forces.has_key(ForceId::Run)
             && ground_sensor.grounded()
         {
             let run_vector = forces.get_vector(ForceId::Run);
 
             if let Some(vector) = run_vector {
-                forces.remove(ForceId::Run);
-
                 commands.entity(entity).insert(Sliding);
                 forces.add(
                     ForceId::Slide,
@@ -111,23 +180,77 @@ fn enter_sliding(
 }
 
 fn handle_sliding_momentum(
+    mut commands: Commands,
     time: Res<Time>,
-    mut player_query: Query<(&mut Forces, &GroundSensor), With<Sliding>>,
+    mut slide_query: Query<
+        (
+            Entity,
+            &mut Forces,
+            &GroundSensor,
+            &MoveDirection,
+            Option<&Slider>,
+        ),
+        With<Sliding>,
+    >,
 ) {
-    for (mut forces, sensor) in &mut player_query {
-        let sliding_force = forces.get_vector(ForceId::Slide);
-        if let Some(force) = sliding_force {
-            if sensor.get_surface_angle() < 5.0 {
-                if force.length() < 5.0 {
-                    forces.add_to(ForceId::Slide, force * -0.5 * time.delta_seconds());
-                }
-            } else {
-                forces.add_to(
-                    ForceId::Slide,
-                    sensor.get_slope_gradient() * time.delta_seconds(),
-                );
-            }
+    for (entity, mut forces, sensor, direction, slider) in &mut slide_query {
+        let tuning = slider.map_or_else(SlideTuning::default, |slider| *slider.tuning());
+        let Some(slide) = forces.get_vector(ForceId::Slide) else {
+            commands.entity(entity).remove::<Sliding>();
+            continue;
+        };
+
+        // Leaving the ground ends the slide, its force carries on until landing
+        if !sensor.grounded() {
+            commands.entity(entity).remove::<Sliding>();
+            continue;
         }
+
+        let on_slope = sensor.get_surface_angle() > 5.0;
+        let mut slide = if on_slope {
+            slide
+                + sensor.get_slope_gradient().normalize_or_zero()
+                    * tuning.slope_acceleration
+                    * time.delta_seconds()
+        } else {
+            let speed = (slide.length() - tuning.friction * time.delta_seconds()).max(0.0);
+            slide.normalize_or_zero() * speed
+        };
+
+        let heading = flatten(slide);
+        let target = flatten(direction.0).normalize_or_zero();
+        if target != Vec3::ZERO && heading != Vec3::ZERO {
+            let turn = heading
+                .angle_between(target)
+                .min(tuning.steering * time.delta_seconds());
+            let turn = turn * heading.cross(target).y.signum();
+            slide = Quat::from_rotation_y(turn) * heading + Vec3::Y * slide.y;
+        }
+
+        // A slope can slow a slide to a halt on its way to turning it around, only flat ground stops it
+        if !on_slope && flatten(slide).length() < tuning.stop_speed {
+            forces.remove(ForceId::Slide);
+            commands.entity(entity).remove::<Sliding>();
+        } else {
+            forces.add(
+                ForceId::Slide,
+                Force::new(slide, None, ForceDecayType::Manual),
+            );
+        }
+    }
+}
+
+fn announce_slides(
+    started_query: Query<Entity, Added<Sliding>>,
+    mut removed: RemovedComponents<Sliding>,
+    mut started_events: EventWriter<SlideStarted>,
+    mut ended_events: EventWriter<SlideEnded>,
+) {
+    for entity in &started_query {
+        started_events.send(SlideStarted { entity });
+    }
+    for entity in removed.read() {
+        ended_events.send(SlideEnded { entity });
     }
 }
 
@@ -137,27 +260,416 @@ fn jump(
         (
             Entity,
             &mut Forces,
+            &mut Transform,
             &InputBuffer,
             &GroundSensor,
-            &Jumper,
+            &mut Jumper,
+            &Speed,
+            &MoveDirection,
+            Option<&GroundPounder>,
             Has<Sliding>,
         ),
         With<Player>,
     >,
 ) {
-    for (entity, mut forces, buffer, sensor, jumper, is_sliding) in &mut query {
-        if buffer.just_pressed(PlayerAction::Jump) && sensor.grounded() {
-            if is_sliding {
-                commands.entity(entity).remove::<Sliding>();
-            }
+    for (
+        entity,
+        mut forces,
+        mut transform,
+        buffer,
+        sensor,
+        mut jumper,
+        speed,
+        direction,
+        pounder,
+        is_sliding,
+    ) in &mut query
+    {
+        // The buffered press stays live for a few ticks, don't let it override a special jump
+        let special_jump = jumper.kind().is_some_and(|kind| kind != JumpKind::Chain);
+        let recovering = pounder.is_some_and(GroundPounder::locks_jump);
+        if !buffer.just_pressed(PlayerAction::Jump)
+            || !sensor.can_jump()
+            || special_jump
+            || recovering
+        {
+            continue;
+        }
+
+        if is_sliding {
+            commands.entity(entity).remove::<Sliding>();
+        }
+
+        if sensor.on_steep_slope() {
+            let scale = sensor.slopes().steep_jump_scale;
             forces.add(
                 ForceId::Jump,
                 Force::new(
-                    Vec3::Y * jumper.get_force(),
+                    Vec3::Y * jumper.get_force() * scale,
                     Some(0.15),
                     ForceDecayType::Manual,
                 ),
             );
+            jumper.start(JumpKind::Steep);
+            continue;
+        }
+
+        // Crouching while running starts a slide right away, so the run may already be a slide
+        let tuning = *jumper.long_jump_tuning();
+        let run_direction = forces
+            .get_vector(ForceId::Run)
+            .or_else(|| forces.get_vector(ForceId::Slide))
+            .map(|vector| Vec3::new(vector.x, 0.0, vector.z).normalize_or_zero())
+            .unwrap_or(Vec3::ZERO);
+        if buffer.pressed(PlayerAction::Crouch)
+            && run_direction != Vec3::ZERO
+            && speed.current() >= tuning.min_speed
+        {
+            forces.add(
+                ForceId::LongJump,
+                Force::new(
+                    run_direction * speed.current() * tuning.boost,
+                    None,
+                    ForceDecayType::Manual,
+                ),
+            );
+            forces.add(
+                ForceId::Jump,
+                Force::new(
+                    Vec3::Y * tuning.jump_force,
+                    Some(0.15),
+                    ForceDecayType::Manual,
+                ),
+            );
+            jumper.start(JumpKind::Long);
+            continue;
+        }
+
+        if buffer.pressed(PlayerAction::Crouch) && run_direction == Vec3::ZERO {
+            let profile = *jumper.backflip_profile();
+            let backward = -transform.forward();
+            add_flip(&mut forces, &profile, backward);
+            jumper.start(JumpKind::Backflip);
+            continue;
+        }
+
+        // Flicking the stick back while skidding to a stop turns the jump into a side somersault
+        let new_direction = Vec3::new(direction.0.x, 0.0, direction.0.z).normalize_or_zero();
+        let reversing = forces.get_vector(ForceId::Skid).is_some_and(|skid| {
+            let skid = Vec3::new(skid.x, 0.0, skid.z).normalize_or_zero();
+            new_direction.dot(skid) < -0.5
+        });
+        if reversing {
+            let profile = *jumper.side_somersault_profile();
+            forces.remove(ForceId::Skid);
+            add_flip(&mut forces, &profile, new_direction);
+            let target = transform.translation + new_direction;
+            transform.look_at(target, Vec3::Y);
+            jumper.start(JumpKind::SideSomersault);
+            continue;
+        }
+
+        forces.add(
+            ForceId::Jump,
+            Force::new(
+                Vec3::Y * jumper.get_force(),
+                Some(0.15),
+                ForceDecayType::Manual,
+            ),
+        );
+        jumper.start(JumpKind::Chain);
+    }
+}
+
+fn add_flip(forces: &mut Forces, profile: &JumpProfile, direction: Vec3) {
+    forces.add(
+        ForceId::Flip,
+        Force::new(
+            direction * profile.lateral_force,
+            None,
+            ForceDecayType::Manual,
+        ),
+    );
+    forces.add(
+        ForceId::Jump,
+        Force::new(
+            Vec3::Y * profile.jump_force,
+            Some(0.15),
+            ForceDecayType::Manual,
+        ),
+    );
+}
+
+fn wall_kick(
+    mut query: Query<
+        (
+            &mut Forces,
+            &mut WallSensor,
+            &mut Transform,
+            &mut InputBuffer,
+            &GroundSensor,
+        ),
+        With<Player>,
+    >,
+) {
+    for (mut forces, mut wall_sensor, mut transform, mut buffer, ground_sensor) in &mut query {
+        // A late jump off a ledge takes priority over kicking a wall next to it
+        if ground_sensor.can_jump() || !buffer.just_pressed(PlayerAction::Jump) {
+            continue;
+        }
+        let Some(contact) = wall_sensor.kickable() else {
+            continue;
+        };
+
+        // One press, one kick, the buffered press mustn't carry over to the next wall
+        buffer.consume(PlayerAction::Jump);
+        let tuning = *wall_sensor.tuning();
+        let normal = Vec3::new(contact.normal.x, 0.0, contact.normal.z).normalize_or_zero();
+        let heading = forces.get_combined_force();
+        let heading = Vec3::new(heading.x, 0.0, heading.z);
+        let reflected = heading - 2.0 * heading.dot(normal) * normal;
+        // Running straight into the wall reflects to nothing useful, push off the wall instead
+        let away = if reflected.dot(normal) > 0.0 {
+            reflected
+        } else {
+            normal
+        };
+        let kick = away.normalize_or_zero() * heading.length().max(tuning.min_speed);
+
+        forces.remove(ForceId::Drift);
+        forces.remove(ForceId::Gravity);
+        forces.add(
+            ForceId::WallKick,
+            Force::new(kick, None, ForceDecayType::Manual),
+        );
+        forces.add(
+            ForceId::Jump,
+            Force::new(
+                Vec3::Y * tuning.jump_force,
+                Some(0.15),
+                ForceDecayType::Manual,
+            ),
+        );
+        let target = transform.translation + kick;
+        transform.look_at(target, Vec3::Y);
+        wall_sensor.kick(contact.wall);
+    }
+}
+
+fn flatten(vector: Vec3) -> Vec3 {
+    Vec3::new(vector.x, 0.0, vector.z)
+}
+
+fn dive(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &mut Diver,
+            &mut Forces,
+            &mut InputBuffer,
+            &mut Jumper,
+            &GroundSensor,
+            &Transform,
+            Option<&KinematicCharacterControllerOutput>,
+            Option<&GroundPounder>,
+            Has<Sliding>,
+        ),
+        With<Player>,
+    >,
+) {
+    for (
+        entity,
+        mut diver,
+        mut forces,
+        mut buffer,
+        mut jumper,
+        sensor,
+        transform,
+        output,
+        pounder,
+        is_sliding,
+    ) in &mut query
+    {
+        let tuning = *diver.tuning();
+        match diver.state() {
+            DiveState::Idle => {
+                let pounding = pounder.is_some_and(GroundPounder::is_pounding);
+                let can_dive = forces.has_key(ForceId::Run) || !sensor.grounded();
+                if !buffer.just_pressed(PlayerAction::Interact) || pounding || !can_dive {
+                    continue;
+                }
+                buffer.consume(PlayerAction::Interact);
+
+                let facing = flatten(transform.forward()).normalize_or_zero();
+                let speed = flatten(forces.get_combined_force())
+                    .length()
+                    .max(tuning.speed);
+                forces.remove_now(ForceId::Skid);
+                forces.remove_now(ForceId::Drift);
+                forces.add(
+                    ForceId::Dive,
+                    Force::new(facing * speed, None, ForceDecayType::Manual),
+                );
+                if sensor.grounded() {
+                    forces.add(
+                        ForceId::Jump,
+                        Force::new(
+                            Vec3::Y * tuning.hop_force,
+                            Some(0.15),
+                            ForceDecayType::Manual,
+                        ),
+                    );
+                }
+                jumper.start(JumpKind::Dive);
+                diver.start_dive();
+            }
+            DiveState::Diving => {
+                // The jumper lets go of the dive once the ground sensor lands it
+                if !sensor.grounded() || jumper.kind() == Some(JumpKind::Dive) {
+                    continue;
+                }
+                match forces.get_vector(ForceId::Dive) {
+                    Some(dive) => {
+                        forces.add(
+                            ForceId::Slide,
+                            Force::new(flatten(dive), None, ForceDecayType::Manual),
+                        );
+                        commands.entity(entity).insert(Sliding);
+                        diver.start_belly_slide();
+                    }
+                    None => diver.stand_up(),
+                }
+            }
+            DiveState::BellySliding => {
+                // `handle_sliding_momentum` ends the slide once friction wears it off
+                if !is_sliding {
+                    diver.stand_up();
+                    continue;
+                }
+                let slide = forces.get_vector(ForceId::Slide).unwrap_or(Vec3::ZERO);
+
+                if buffer.just_pressed(PlayerAction::Jump)
+                    || buffer.just_pressed(PlayerAction::Interact)
+                {
+                    buffer.consume(PlayerAction::Jump);
+                    buffer.consume(PlayerAction::Interact);
+                    commands.entity(entity).remove::<Sliding>();
+                    forces.add(
+                        ForceId::Dive,
+                        Force::new(flatten(slide) * 0.5, None, ForceDecayType::Manual),
+                    );
+                    forces.add(
+                        ForceId::Jump,
+                        Force::new(
+                            Vec3::Y * tuning.roll_out_force,
+                            Some(0.15),
+                            ForceDecayType::Manual,
+                        ),
+                    );
+                    jumper.start(JumpKind::RollOut);
+                    diver.roll_out();
+                    continue;
+                }
+
+                // Sliding off a ledge carries on as a dive
+                if !sensor.grounded() {
+                    commands.entity(entity).remove::<Sliding>();
+                    forces.add(
+                        ForceId::Dive,
+                        Force::new(flatten(slide), None, ForceDecayType::Manual),
+                    );
+                    jumper.start(JumpKind::Dive);
+                    diver.start_dive();
+                    continue;
+                }
+
+                // Barely moving despite pushing forward means a wall is in the way
+                let blocked = output.is_some_and(|output| {
+                    let desired = flatten(output.desired_translation).length();
+                    desired > 0.01
+                        && flatten(output.effective_translation).length() < desired * 0.25
+                });
+                if blocked {
+                    forces.remove(ForceId::Slide);
+                    commands.entity(entity).remove::<Sliding>();
+                    diver.stand_up();
+                }
+            }
+            DiveState::RollingOut => {
+                if sensor.grounded() && jumper.kind() != Some(JumpKind::RollOut) {
+                    forces.remove(ForceId::Dive);
+                    diver.stand_up();
+                }
+            }
+        }
+    }
+}
+
+fn ground_pound(
+    time: Res<Time>,
+    mut landed_events: EventWriter<GroundPoundLanded>,
+    mut query: Query<
+        (
+            Entity,
+            &mut GroundPounder,
+            &mut Forces,
+            &mut InputBuffer,
+            &GroundSensor,
+            &Transform,
+        ),
+        With<Player>,
+    >,
+) {
+    for (entity, mut pounder, mut forces, mut buffer, sensor, transform) in &mut query {
+        match pounder.phase() {
+            GroundPoundPhase::Idle => {
+                if !buffer.just_pressed(PlayerAction::Crouch) {
+                    continue;
+                }
+                // A Crouch pressed on the ground belongs to a slide or a flip, don't let the
+                // buffered press start a pound right after takeoff
+                buffer.consume(PlayerAction::Crouch);
+                if sensor.grounded() {
+                    continue;
+                }
+                forces.remove_now(ForceId::Jump);
+                forces.remove_now(ForceId::Drift);
+                forces.add(
+                    ForceId::GroundPound,
+                    Force::new(Vec3::ZERO, None, ForceDecayType::Manual),
+                );
+                pounder.start();
+            }
+            GroundPoundPhase::Hovering | GroundPoundPhase::Falling => {
+                if sensor.grounded() {
+                    let fall_speed = forces
+                        .get_vector(ForceId::GroundPound)
+                        .map_or(0.0, |force| -force.y);
+                    forces.remove(ForceId::GroundPound);
+                    pounder.land();
+                    landed_events.send(GroundPoundLanded {
+                        entity,
+                        position: transform.translation,
+                        fall_speed,
+                    });
+                    continue;
+                }
+
+                pounder.tick(time.delta());
+                // The pound replaces gravity entirely, it's re-added every tick. Drift would build
+                // up behind the mask and carry the character sideways once the pound ends.
+                forces.remove_now(ForceId::Gravity);
+                forces.remove_now(ForceId::Drift);
+                if pounder.phase() == GroundPoundPhase::Falling {
+                    let fall_speed = pounder.tuning().fall_speed;
+                    forces.add(
+                        ForceId::GroundPound,
+                        Force::new(Vec3::NEG_Y * fall_speed, None, ForceDecayType::Manual),
+                    );
+                }
+            }
+            GroundPoundPhase::Recovering => pounder.tick(time.delta()),
         }
     }
 }
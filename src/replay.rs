@@ -0,0 +1,167 @@
+use crate::{
+    input::{buffer_inputs, InputBuffer, InputFrame, PlayerAction},
+    types::EngineSystemSet,
+};
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{fmt, path::Path};
+
+/// Bump whenever `Replay` or `InputFrame` change shape
+pub const REPLAY_VERSION: u32 = 1;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (play_back_input.after(buffer_inputs), record_input)
+                .chain()
+                .in_set(EngineSystemSet::Input),
+        );
+    }
+}
+
+/// Input captured one frame per fixed tick, along with the timestep it was captured at
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub timestep: f64,
+    pub frames: Vec<InputFrame>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::error::SpannedError),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not access replay file: {error}"),
+            Self::Serialize(error) => write!(f, "could not write replay: {error}"),
+            Self::Deserialize(error) => write!(f, "could not read replay: {error}"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "replay version {version} is not supported, expected {REPLAY_VERSION}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<std::io::Error> for ReplayError {
+    fn from(error: std::io::Error) -> Self {
+        ReplayError::Io(error)
+    }
+}
+
+impl Replay {
+    pub fn new(timestep: f64, frames: Vec<InputFrame>) -> Self {
+        Replay {
+            version: REPLAY_VERSION,
+            timestep,
+            frames,
+        }
+    }
+
+    pub fn to_ron(&self) -> Result<String, ReplayError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(ReplayError::Serialize)
+    }
+
+    pub fn from_ron(source: &str) -> Result<Self, ReplayError> {
+        let replay: Replay = ron::from_str(source).map_err(ReplayError::Deserialize)?;
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(replay.version));
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        std::fs::write(path, self.to_ron()?)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        Replay::from_ron(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// Captures the live input of its entity every fixed tick
+#[derive(Component, Default)]
+pub struct InputRecorder {
+    frames: Vec<InputFrame>,
+}
+
+impl InputRecorder {
+    pub fn frames(&self) -> &[InputFrame] {
+        &self.frames
+    }
+
+    pub fn to_replay(&self, timestep: f64) -> Replay {
+        Replay::new(timestep, self.frames.clone())
+    }
+}
+
+/// Feeds a replay into the `InputBuffer` instead of live input, removed once it runs out
+#[derive(Component)]
+pub struct InputPlayback {
+    frames: Vec<InputFrame>,
+    cursor: usize,
+}
+
+impl InputPlayback {
+    pub fn new(replay: &Replay) -> Self {
+        InputPlayback {
+            frames: replay.frames.clone(),
+            cursor: 0,
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.cursor >= self.frames.len()
+    }
+
+    fn next_frame(&mut self) -> Option<&InputFrame> {
+        let frame = self.frames.get(self.cursor);
+        self.cursor += 1;
+        frame
+    }
+}
+
+pub(crate) fn record_input(
+    mut recorder_query: Query<
+        (&mut InputRecorder, &ActionState<PlayerAction>, &InputBuffer),
+        Without<InputPlayback>,
+    >,
+) {
+    for (mut recorder, action_state, buffer) in &mut recorder_query {
+        recorder.frames.push(InputFrame {
+            pressed: action_state.get_pressed(),
+            move_axis: buffer.move_axis(),
+        });
+    }
+}
+
+pub(crate) fn play_back_input(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut playback_query: Query<(Entity, &mut InputPlayback, &mut InputBuffer)>,
+) {
+    for (entity, mut playback, mut buffer) in &mut playback_query {
+        buffer.tick(time.delta());
+        match playback.next_frame() {
+            Some(frame) => buffer.apply_frame(frame),
+            None => {
+                buffer.apply_frame(&InputFrame::default());
+                commands.entity(entity).remove::<InputPlayback>();
+            }
+        }
+    }
+}
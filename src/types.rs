@@ -1,5 +1,6 @@
-use bevy::{prelude::*, utils::HashMap};
-use std::collections::HashSet;
+use bevy::{prelude::*, time::Stopwatch, utils::HashMap};
+use bevy_rapier3d::prelude::{CollisionGroups, Group, KinematicCharacterController};
+use std::{any::TypeId, collections::VecDeque};
 
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub enum EngineSystemSet {
@@ -8,10 +9,70 @@ pub enum EngineSystemSet {
     ApplyMomentum,
 }
 
+/// Phases of `EngineSystemSet::CalculateMomentum`. Nearly every system there writes `Forces`, so
+/// they all need a fixed order for fixed-step runs to be reproducible.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub enum MomentumSystemSet {
+    Vertical,
+    Moveset,
+    Lateral,
+}
+
 #[derive(Component)]
 pub struct Sliding;
 
-#[derive(Default)]
+/// Tuning shared by every kind of slide
+#[derive(Clone, Copy)]
+pub struct SlideTuning {
+    /// Speed lost per second on flat ground
+    pub friction: f32,
+    /// Speed gained per second down a slope
+    pub slope_acceleration: f32,
+    /// Slides slower than this end on their own
+    pub stop_speed: f32,
+    /// Radians per second the slide can be turned towards `MoveDirection`
+    pub steering: f32,
+}
+
+impl Default for SlideTuning {
+    fn default() -> Self {
+        SlideTuning {
+            friction: 10.0,
+            slope_acceleration: 20.0,
+            stop_speed: 2.0,
+            steering: 1.5,
+        }
+    }
+}
+
+/// Overrides the default `SlideTuning` of a character
+#[derive(Component, Default)]
+pub struct Slider {
+    tuning: SlideTuning,
+}
+
+impl Slider {
+    pub fn with_slide(mut self, tuning: SlideTuning) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
+    pub fn tuning(&self) -> &SlideTuning {
+        &self.tuning
+    }
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct SlideStarted {
+    pub entity: Entity,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct SlideEnded {
+    pub entity: Entity,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum CarryWeight {
     #[default]
     Light,
@@ -19,29 +80,143 @@ pub enum CarryWeight {
     Heavy,
 }
 
+impl CarryWeight {
+    pub fn speed_scale(&self) -> f32 {
+        match self {
+            Self::Light => 0.9,
+            Self::Medium => 0.7,
+            Self::Heavy => 0.45,
+        }
+    }
+
+    pub fn jump_scale(&self) -> f32 {
+        match self {
+            Self::Light => 0.9,
+            Self::Medium => 0.75,
+            Self::Heavy => 0.5,
+        }
+    }
+
+    pub fn throw_scale(&self) -> f32 {
+        match self {
+            Self::Light => 1.0,
+            Self::Medium => 0.7,
+            Self::Heavy => 0.4,
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Carryable {
     pub weight: CarryWeight,
+    /// Where the object sits while carried, in the carrier's local space
     pub offset: Vec3,
 }
 
+/// Marks a `Carryable` that is currently being carried
+#[derive(Component)]
+pub struct Held {
+    pub carrier: Entity,
+    /// The object's own collision groups, put back when it's thrown
+    pub collision_groups: Option<CollisionGroups>,
+}
+
+/// Lets a character pick up and throw `Carryable`s with `PlayerAction::Interact`
+#[derive(Component)]
+pub struct Carrier {
+    held: Option<Entity>,
+    /// The controller's own filter, put back when the held object is thrown
+    filter_groups: Option<CollisionGroups>,
+    pub reach: f32,
+    pub throw_speed: f32,
+    pub throw_lift: f32,
+    /// Held objects move into this group, which the carrier's movement leaves out. Pick one the
+    /// game doesn't use for anything else.
+    pub held_group: Group,
+}
+
+impl Carrier {
+    pub fn held(&self) -> Option<Entity> {
+        self.held
+    }
+
+    pub fn is_carrying(&self) -> bool {
+        self.held.is_some()
+    }
+
+    pub fn set_held(&mut self, held: Option<Entity>) {
+        self.held = held;
+    }
+
+    /// Leaves the held group out of `controller`'s filter, keeping whatever it filtered before
+    pub fn filter_out_held(&mut self, controller: &mut KinematicCharacterController) {
+        self.filter_groups = controller.filter_groups;
+        let groups = controller
+            .filter_groups
+            .unwrap_or(CollisionGroups::new(Group::ALL, Group::ALL));
+        controller.filter_groups = Some(CollisionGroups::new(
+            groups.memberships,
+            groups.filters & !self.held_group,
+        ));
+    }
+
+    /// Puts back the filter `filter_out_held` replaced
+    pub fn restore_filter(&mut self, controller: &mut KinematicCharacterController) {
+        controller.filter_groups = self.filter_groups.take();
+    }
+}
+
+impl Default for Carrier {
+    fn default() -> Self {
+        Carrier {
+            held: None,
+            filter_groups: None,
+            reach: 1.75,
+            throw_speed: 14.0,
+            throw_lift: 4.0,
+            held_group: Group::GROUP_32,
+        }
+    }
+}
+
+/// Downward acceleration in units per second squared
 #[derive(Resource)]
 pub struct Gravity {
     force: Vec3,
+    terminal_velocity: Option<f32>,
 }
 
 impl Gravity {
     pub fn new(amount: f32) -> Self {
         let force = Vec3::NEG_Y * amount;
 
-        Gravity { force }
+        Gravity {
+            force,
+            terminal_velocity: None,
+        }
+    }
+
+    /// Caps how fast gravity alone can make anything fall
+    pub fn with_terminal_velocity(mut self, speed: f32) -> Self {
+        self.terminal_velocity = Some(speed);
+        self
     }
 
     pub fn force(&self) -> Vec3 {
         self.force
     }
+
+    pub fn terminal_velocity(&self) -> Option<f32> {
+        self.terminal_velocity
+    }
 }
 
+/// Overrides the `Gravity` terminal velocity for one entity. `None`, the default, keeps the
+/// `Gravity` one.
+#[derive(Component, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct TerminalVelocity(pub Option<f32>);
+
 #[derive(Reflect, Default, Component)]
 #[reflect(Component)]
 pub struct Momentum(pub Vec3);
@@ -98,7 +273,7 @@ impl Momentum {
 #[derive(Component)]
 pub struct GravityAffected;
 
-#[derive(Default, PartialEq)]
+#[derive(Default, PartialEq, Debug, Reflect)]
 pub enum JumpStage {
     #[default]
     Single,
@@ -116,10 +291,80 @@ impl JumpStage {
     }
 }
 
-#[derive(Component, Default)]
+/// Tuning for the long jump, started with Crouch + Jump while running
+#[derive(Clone, Copy, Reflect)]
+pub struct LongJumpTuning {
+    /// `Speed::current()` needed before a long jump can start
+    pub min_speed: f32,
+    pub jump_force: f32,
+    /// Horizontal force per unit of run speed
+    pub boost: f32,
+    /// Multiplier on `Drift` while long jumping
+    pub drift_scale: f32,
+}
+
+impl Default for LongJumpTuning {
+    fn default() -> Self {
+        LongJumpTuning {
+            min_speed: 12.0,
+            jump_force: 7.0,
+            boost: 1.5,
+            drift_scale: 0.3,
+        }
+    }
+}
+
+/// The kind of jump in progress. Only `Chain` jumps take part in the `JumpStage` triple jump.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+pub enum JumpKind {
+    Chain,
+    Long,
+    Backflip,
+    SideSomersault,
+    Dive,
+    RollOut,
+    /// A weak hop off a slope too steep to stand on
+    Steep,
+}
+
+/// Force profile of a jump that sits outside the triple-jump chain
+#[derive(Clone, Copy, Reflect)]
+pub struct JumpProfile {
+    pub jump_force: f32,
+    /// Horizontal force along the direction the jump sends the character
+    pub lateral_force: f32,
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
 pub struct Jumper {
     stage: JumpStage,
     increase_timer: Timer,
+    kind: Option<JumpKind>,
+    long_jump: LongJumpTuning,
+    backflip: JumpProfile,
+    side_somersault: JumpProfile,
+    force_scale: f32,
+}
+
+impl Default for Jumper {
+    fn default() -> Self {
+        Jumper {
+            stage: JumpStage::default(),
+            increase_timer: Timer::default(),
+            kind: None,
+            long_jump: LongJumpTuning::default(),
+            backflip: JumpProfile {
+                jump_force: 15.0,
+                lateral_force: 3.0,
+            },
+            side_somersault: JumpProfile {
+                jump_force: 14.0,
+                lateral_force: 4.0,
+            },
+            force_scale: 1.0,
+        }
+    }
 }
 
 impl Jumper {
@@ -132,6 +377,21 @@ impl Jumper {
     }
 
     pub fn land(&mut self) {
+        match self.kind.take() {
+            Some(JumpKind::Long) => self.reset(),
+            // Flips and dives sit outside the chain, landing one leaves it where it was
+            Some(
+                JumpKind::Backflip
+                | JumpKind::SideSomersault
+                | JumpKind::Dive
+                | JumpKind::RollOut
+                | JumpKind::Steep,
+            ) => {}
+            Some(JumpKind::Chain) | None => self.advance(),
+        }
+    }
+
+    fn advance(&mut self) {
         let (new_stage, new_timer) = match self.stage {
             JumpStage::Single => (
                 JumpStage::Double,
@@ -148,17 +408,73 @@ impl Jumper {
     }
 
     pub fn get_force(&self) -> f32 {
-        self.stage.get_jump_force()
+        self.stage.get_jump_force() * self.force_scale
+    }
+
+    /// Scales every jump, e.g. while carrying something heavy
+    pub fn set_force_scale(&mut self, scale: f32) {
+        self.force_scale = scale;
+    }
+
+    pub fn stage(&self) -> &JumpStage {
+        &self.stage
+    }
+
+    pub fn reset(&mut self) {
+        self.stage = JumpStage::Single;
+        self.increase_timer = Timer::default();
+    }
+
+    pub fn with_long_jump(mut self, tuning: LongJumpTuning) -> Self {
+        self.long_jump = tuning;
+        self
+    }
+
+    pub fn with_backflip(mut self, profile: JumpProfile) -> Self {
+        self.backflip = profile;
+        self
+    }
+
+    pub fn with_side_somersault(mut self, profile: JumpProfile) -> Self {
+        self.side_somersault = profile;
+        self
+    }
+
+    pub fn long_jump_tuning(&self) -> &LongJumpTuning {
+        &self.long_jump
+    }
+
+    pub fn backflip_profile(&self) -> &JumpProfile {
+        &self.backflip
+    }
+
+    pub fn side_somersault_profile(&self) -> &JumpProfile {
+        &self.side_somersault
+    }
+
+    /// Records the kind of jump that just started, deciding what landing it does to the chain
+    pub fn start(&mut self, kind: JumpKind) {
+        self.kind = Some(kind);
+    }
+
+    pub fn kind(&self) -> Option<JumpKind> {
+        self.kind
+    }
+
+    pub fn is_long_jumping(&self) -> bool {
+        self.kind == Some(JumpKind::Long)
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
 pub struct Speed {
     current: f32,
     accel: f32,
     base: f32,
     max: f32,
     base_max: f32,
+    max_scale: f32,
     accel_timer: Timer,
     reset_timer: Timer,
 }
@@ -168,12 +484,23 @@ impl Speed {
         self.current
     }
 
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
     pub fn reset(&mut self) {
         self.current = self.base;
-        self.max = self.base_max;
+        self.max = self.base_max * self.max_scale;
         self.accel_timer.reset();
     }
 
+    /// Scales the top speed until set back to 1.0, e.g. while carrying something heavy
+    pub fn set_max_scale(&mut self, scale: f32) {
+        self.max_scale = scale;
+        self.max = self.base_max * scale;
+        self.current = self.current.min(self.max);
+    }
+
     pub fn tick_reset_timer(&mut self, delta: std::time::Duration) {
         self.reset_timer.tick(delta);
     }
@@ -190,7 +517,9 @@ impl Speed {
         self.accel_timer.tick(delta);
         if self.accel_timer.finished() {
             if self.current < self.max {
-                self.current = self.current + (self.max - self.current) * (seconds * self.accel);
+                // Closes the gap to max exponentially, exact for any step size
+                let remaining = (self.max - self.current) * (-seconds * self.accel).exp();
+                self.current = self.max - remaining;
             } else {
                 self.current = self.max;
             }
@@ -206,13 +535,15 @@ impl Default for Speed {
             accel: 2.5,
             max: 20.0,
             base_max: 20.0,
+            max_scale: 1.0,
             accel_timer: Timer::from_seconds(0.3, TimerMode::Once),
             reset_timer: Timer::from_seconds(0.1, TimerMode::Once),
         }
     }
 }
 
-#[derive(Default, Component)]
+#[derive(Default, Component, Reflect)]
+#[reflect(Component)]
 pub struct MoveDirection(pub Vec3);
 
 impl MoveDirection {
@@ -237,34 +568,111 @@ impl MoveDirection {
     }
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Debug, Reflect)]
 pub enum ForceDecayType {
     Automatic,
     Manual,
 }
 
-#[derive(Component)]
+/// How a force's magnitude falls off over its lifespan. Forces without a lifespan always apply in
+/// full.
+#[derive(Clone, PartialEq, Debug, Default, Reflect)]
+pub enum ForceFalloff {
+    #[default]
+    Constant,
+    Linear,
+    /// Scales by `e^(-rate * progress)`
+    Exponential {
+        rate: f32,
+    },
+    /// Scales by `(1 - progress)²`, dropping fastest right after it starts and levelling off as
+    /// it nears zero
+    EaseOut,
+    /// Scales spaced evenly over the lifespan, interpolated linearly in between
+    Keyframes(Vec<f32>),
+}
+
+impl ForceFalloff {
+    /// The scale at `progress`, from 0 when the force is added to 1 when it expires
+    pub fn sample(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+        match self {
+            ForceFalloff::Constant => 1.0,
+            ForceFalloff::Linear => 1.0 - progress,
+            ForceFalloff::Exponential { rate } => (-rate * progress).exp(),
+            ForceFalloff::EaseOut => (1.0 - progress).powi(2),
+            ForceFalloff::Keyframes(keys) => match keys.len() {
+                0 => 1.0,
+                1 => keys[0],
+                count => {
+                    let position = progress * (count - 1) as f32;
+                    let index = (position as usize).min(count - 2);
+                    let t = position - index as f32;
+                    keys[index] + (keys[index + 1] - keys[index]) * t
+                }
+            },
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
 pub struct Force {
     applied_force: Vec3,
     expiration_timer: Option<Timer>,
     decay_type: ForceDecayType,
+    falloff: ForceFalloff,
+    max_magnitude: Option<f32>,
+    /// Set by `Forces::remove` on a `Manual` force that still has lifetime left
+    pending_removal: bool,
 }
 
 impl Force {
     pub fn new(applied_force: Vec3, lifespan: Option<f32>, decay_type: ForceDecayType) -> Self {
-        let expiration_timer = if let Some(total_lifetime) = lifespan {
-            Some(Timer::from_seconds(total_lifetime, TimerMode::Once))
-        } else {
-            None
-        };
+        let expiration_timer =
+            lifespan.map(|total_lifetime| Timer::from_seconds(total_lifetime, TimerMode::Once));
 
         Force {
             applied_force,
             expiration_timer,
             decay_type,
+            falloff: ForceFalloff::Constant,
+            max_magnitude: None,
+            pending_removal: false,
+        }
+    }
+
+    pub fn with_falloff(mut self, falloff: ForceFalloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Keeps the force from ever growing past `max`, including through `add_force`
+    pub fn with_max_magnitude(mut self, max: f32) -> Self {
+        self.set_max_magnitude(Some(max));
+        self
+    }
+
+    /// Changes the cap, returns whether the force had to be cut back to fit under it
+    pub fn set_max_magnitude(&mut self, max: Option<f32>) -> bool {
+        self.max_magnitude = max;
+        match max {
+            Some(max) if self.applied_force.length() > max => {
+                self.applied_force = self.applied_force.clamp_length_max(max);
+                true
+            }
+            _ => false,
         }
     }
 
+    /// The force after falloff, as it's applied this tick
+    pub fn current(&self) -> Vec3 {
+        let progress = self
+            .expiration_timer
+            .as_ref()
+            .map_or(0.0, |timer| timer.percent());
+        self.applied_force * self.falloff.sample(progress)
+    }
+
     pub fn tick(&mut self, delta: std::time::Duration) {
         if let Some(timer) = self.expiration_timer.as_mut() {
             timer.tick(delta);
@@ -279,8 +687,16 @@ impl Force {
         }
     }
 
-    pub fn add_force(&mut self, force: Vec3) {
+    /// Adds to the force, returns whether it had to be clamped to its max magnitude
+    pub fn add_force(&mut self, force: Vec3) -> bool {
         self.applied_force += force;
+        match self.max_magnitude {
+            Some(max) if self.applied_force.length() > max => {
+                self.applied_force = self.applied_force.clamp_length_max(max);
+                true
+            }
+            _ => false,
+        }
     }
 
     pub fn add_time(&mut self, seconds: f32) {
@@ -291,7 +707,7 @@ impl Force {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug, Reflect)]
 pub enum ForceId {
     Gravity,
     Run,
@@ -301,24 +717,283 @@ pub enum ForceId {
     Skid,
     Drift,
     Slope,
+    LongJump,
+    WallKick,
+    Flip,
+    GroundPound,
+    Dive,
+    Knockback,
+    /// The velocity of the moving body the character stands on, kept through the air
+    Platform,
+    /// Shimmying along a ledge, the only force a hanging character has
+    Hang,
+    /// A force defined outside this crate, see `ForceId::custom`
+    Custom(CustomForceId),
 }
 
-#[derive(Component, Default)]
+impl ForceId {
+    /// A key of its own for the force type `T`, e.g. `ForceId::custom::<Cannon>()`. Keys compare by
+    /// `TypeId`, so two types never share a key even if their names print the same.
+    pub fn custom<T: ?Sized + 'static>() -> Self {
+        ForceId::Custom(CustomForceId {
+            name: std::any::type_name::<T>(),
+            type_id: TypeId::of::<T>(),
+        })
+    }
+}
+
+/// Identifies a custom force by its type. The type name goes first so keys sort the same way in
+/// every build, `TypeId`s are only stable within one.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug, Reflect)]
+#[reflect_value(PartialEq, Hash, Debug)]
+pub struct CustomForceId {
+    name: &'static str,
+    type_id: TypeId,
+}
+
+impl CustomForceId {
+    /// The type name, for logs and debugging only
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+}
+
+/// The forces that drive the player's own moves, as opposed to the world pushing them around
+pub const PLAYER_FORCES: [ForceId; 10] = [
+    ForceId::Run,
+    ForceId::Jump,
+    ForceId::Slide,
+    ForceId::Skid,
+    ForceId::Drift,
+    ForceId::LongJump,
+    ForceId::WallKick,
+    ForceId::Flip,
+    ForceId::GroundPound,
+    ForceId::Dive,
+];
+
+/// Built-in forces that yield to `ForceId::Hang`: the player's own moves and everything that
+/// builds up tick by tick
+const HUNG_FORCES: [ForceId; 12] = [
+    ForceId::Gravity,
+    ForceId::Slope,
+    ForceId::Run,
+    ForceId::Jump,
+    ForceId::Slide,
+    ForceId::Skid,
+    ForceId::Drift,
+    ForceId::LongJump,
+    ForceId::WallKick,
+    ForceId::Flip,
+    ForceId::GroundPound,
+    ForceId::Dive,
+];
+
+/// Declared interactions between forces, so a move doesn't have to clear out every other move by
+/// hand
+#[derive(Clone, Debug)]
+pub struct ForceRules {
+    exclusions: Vec<Vec<ForceId>>,
+    yields: Vec<(ForceId, Vec<ForceId>)>,
+    overrides: Vec<(ForceId, Vec<ForceId>)>,
+    masks: Vec<(ForceId, BVec3)>,
+}
+
+impl Default for ForceRules {
+    fn default() -> Self {
+        let moves = [
+            ForceId::Slide,
+            ForceId::LongJump,
+            ForceId::Flip,
+            ForceId::WallKick,
+            ForceId::Dive,
+            ForceId::GroundPound,
+        ];
+        let rules = ForceRules::none()
+            // Only one move sets the pace along the ground or through the air at a time
+            .exclusive(&moves)
+            // A long jump keeps the run going under it, the same as any other jump
+            .yields(
+                ForceId::Run,
+                &[
+                    ForceId::Slide,
+                    ForceId::Flip,
+                    ForceId::WallKick,
+                    ForceId::Dive,
+                    ForceId::GroundPound,
+                ],
+            )
+            // Sticking to a slope would pin any jump off it to the ground. Runs follow the ground
+            // on their own and would get snapped down at the top of a ramp.
+            .yields(ForceId::Slope, &[ForceId::Jump, ForceId::Run])
+            .overrides(ForceId::Knockback, &PLAYER_FORCES)
+            .masks(ForceId::GroundPound, BVec3::new(false, true, false))
+            // The world's pushes are only held off while hanging, they pick up again on letting go
+            .overrides(ForceId::Hang, &[ForceId::Wind, ForceId::Platform]);
+        // Hanging from a ledge holds the character in place, nothing builds up until it lets go
+        HUNG_FORCES.iter().fold(rules, |rules, force_id| {
+            rules.yields(*force_id, &[ForceId::Hang])
+        })
+    }
+}
+
+impl ForceRules {
+    /// No rules at all, every force applies in full
+    pub fn none() -> Self {
+        ForceRules {
+            exclusions: Vec::new(),
+            yields: Vec::new(),
+            overrides: Vec::new(),
+            masks: Vec::new(),
+        }
+    }
+
+    /// Adding any force in `group` evicts the others in it
+    pub fn exclusive(mut self, group: &[ForceId]) -> Self {
+        self.exclusions.push(group.to_vec());
+        self
+    }
+
+    /// `force_id` can't be added while any of `to` is present, and adding one of them evicts it
+    pub fn yields(mut self, force_id: ForceId, to: &[ForceId]) -> Self {
+        self.yields.push((force_id, to.to_vec()));
+        self
+    }
+
+    /// While `force_id` is present the `overridden` forces are kept but contribute nothing
+    pub fn overrides(mut self, force_id: ForceId, overridden: &[ForceId]) -> Self {
+        self.overrides.push((force_id, overridden.to_vec()));
+        self
+    }
+
+    /// While `force_id` is present every other force only contributes along the `keep` axes
+    pub fn masks(mut self, force_id: ForceId, keep: BVec3) -> Self {
+        self.masks.push((force_id, keep));
+        self
+    }
+
+    fn evicted_by(&self, force_id: ForceId) -> impl Iterator<Item = ForceId> + '_ {
+        let excluded = self
+            .exclusions
+            .iter()
+            .filter(move |group| group.contains(&force_id))
+            .flatten()
+            .copied()
+            .filter(move |other| *other != force_id);
+        let yielding = self
+            .yields
+            .iter()
+            .filter(move |(_, to)| to.contains(&force_id))
+            .map(|(yielding, _)| *yielding);
+        excluded.chain(yielding)
+    }
+
+    fn yields_to(&self, force_id: ForceId) -> impl Iterator<Item = ForceId> + '_ {
+        self.yields
+            .iter()
+            .filter(move |(yielding, _)| *yielding == force_id)
+            .flat_map(|(_, to)| to.iter().copied())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ForceChangeReason {
+    Added,
+    /// Added over a force with the same id
+    Replaced,
+    /// Grown through `Forces::add_to`
+    Accumulated,
+    /// Grown through `Forces::add_to` and cut back to its max magnitude
+    Clamped,
+    /// Its lifespan ran out
+    Expired,
+    Removed,
+    /// Pushed out by a force in the same exclusion group, see `ForceRules`
+    Evicted,
+}
+
+/// Sent whenever a force comes, changes or goes. `vector` is the force after the change, or what
+/// it was when it left, falloff included. Adding or growing a force without changing its vector
+/// doesn't count as a change.
+#[derive(Event, Clone, Debug)]
+pub struct ForceChanged {
+    pub entity: Entity,
+    pub force_id: ForceId,
+    pub vector: Vec3,
+    pub reason: ForceChangeReason,
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
 pub struct Forces {
     forces: HashMap<ForceId, Force>,
-    scheduled_to_remove: HashSet<ForceId>,
+    #[reflect(ignore)]
+    rules: ForceRules,
+    /// Caps the length of the combined force
+    max_combined: Option<f32>,
+    /// Changes since the last `drain_changes`, in the order they happened
+    #[reflect(ignore)]
+    changes: VecDeque<(ForceId, Vec3, ForceChangeReason)>,
 }
 
+/// How many changes `Forces` holds on to before dropping the oldest, in case nothing drains them
+const MAX_LOGGED_CHANGES: usize = 256;
+
 impl Forces {
+    pub fn with_rules(mut self, rules: ForceRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn rules(&self) -> &ForceRules {
+        &self.rules
+    }
+
+    pub fn set_rules(&mut self, rules: ForceRules) {
+        self.rules = rules;
+    }
+
+    pub fn with_max_combined(mut self, max: f32) -> Self {
+        self.max_combined = Some(max);
+        self
+    }
+
+    pub fn set_max_combined(&mut self, max: Option<f32>) {
+        self.max_combined = max;
+    }
+
+    /// The force after its falloff, before any overrides or masks apply
     pub fn get_vector(&self, force_id: ForceId) -> Option<Vec3> {
-        match self.forces.get(&force_id) {
-            Some(force) => Some(force.applied_force),
-            None => None,
-        }
+        self.forces.get(&force_id).map(Force::current)
     }
 
-    pub fn add(&mut self, force_id: ForceId, force: Force) {
-        self.forces.insert(force_id, force);
+    /// Adds or replaces a force, unless the rules have it yield to a force that's already present.
+    /// Returns whether it went in.
+    pub fn add(&mut self, force_id: ForceId, force: Force) -> bool {
+        if self
+            .rules
+            .yields_to(force_id)
+            .any(|other| self.forces.contains_key(&other))
+        {
+            return false;
+        }
+        let evicted: Vec<ForceId> = self.rules.evicted_by(force_id).collect();
+        for evicted in evicted {
+            self.take(evicted, ForceChangeReason::Evicted);
+        }
+        let vector = force.current();
+        match self.forces.insert(force_id, force) {
+            Some(replaced) if replaced.current() != vector => {
+                self.log(force_id, vector, ForceChangeReason::Replaced)
+            }
+            Some(_) => {}
+            None => self.log(force_id, vector, ForceChangeReason::Added),
+        }
+        true
     }
 
     pub fn length(&self) -> usize {
@@ -327,68 +1002,143 @@ impl Forces {
 
     pub fn add_to(&mut self, force_id: ForceId, amount: Vec3) {
         if let Some(force) = self.forces.get_mut(&force_id) {
-            force.add_force(amount);
+            let before = force.current();
+            let reason = if force.add_force(amount) {
+                ForceChangeReason::Clamped
+            } else {
+                ForceChangeReason::Accumulated
+            };
+            let vector = force.current();
+            if vector != before {
+                self.log(force_id, vector, reason);
+            }
         } else {
             self.add(force_id, Force::new(amount, None, ForceDecayType::Manual));
         }
     }
 
-    pub fn remove(&mut self, force_id: ForceId) {
-        let mut can_remove = true;
-        if let Some(force) = self.forces.get(&force_id) {
-            if !force.finished() {
-                can_remove = false;
+    /// Changes the cap on a force that's already present, see `Force::set_max_magnitude`
+    pub fn set_max_magnitude(&mut self, force_id: ForceId, max: Option<f32>) {
+        if let Some(force) = self.forces.get_mut(&force_id) {
+            if force.set_max_magnitude(max) {
+                let vector = force.current();
+                self.log(force_id, vector, ForceChangeReason::Clamped);
             }
         }
-        if can_remove {
-            let _ = self.forces.remove(&force_id);
-        } else {
-            self.scheduled_to_remove.insert(force_id);
+    }
+
+    pub fn remove(&mut self, force_id: ForceId) {
+        match self.forces.get_mut(&force_id) {
+            Some(force) if !force.finished() => force.pending_removal = true,
+            _ => self.take(force_id, ForceChangeReason::Removed),
         }
     }
 
-    pub fn has_key(&self, force_id: ForceId) -> bool {
-        if let Some(_) = self.forces.get(&force_id) {
-            true
-        } else {
-            false
+    /// Drops a force right away, even if its lifespan hasn't run out yet
+    pub fn remove_now(&mut self, force_id: ForceId) {
+        self.take(force_id, ForceChangeReason::Removed);
+    }
+
+    fn take(&mut self, force_id: ForceId, reason: ForceChangeReason) {
+        if let Some(force) = self.forces.remove(&force_id) {
+            self.log(force_id, force.current(), reason);
         }
     }
 
+    fn log(&mut self, force_id: ForceId, vector: Vec3, reason: ForceChangeReason) {
+        if self.changes.len() == MAX_LOGGED_CHANGES {
+            self.changes.pop_front();
+        }
+        self.changes.push_back((force_id, vector, reason));
+    }
+
+    /// Hands over the changes logged since the last call, oldest first. Only the latest 256 are
+    /// kept.
+    pub fn drain_changes(
+        &mut self,
+    ) -> impl Iterator<Item = (ForceId, Vec3, ForceChangeReason)> + '_ {
+        self.changes.drain(..)
+    }
+
+    pub fn has_key(&self, force_id: ForceId) -> bool {
+        self.forces.contains_key(&force_id)
+    }
+
     pub fn reset(&mut self) {
-        self.forces = bevy::utils::HashMap::new();
+        for force_id in self.sorted_ids(|_| true) {
+            self.take(force_id, ForceChangeReason::Removed);
+        }
     }
 
     fn remove_finished_forces(&mut self) {
-        let mut dead_keys: Vec<ForceId> = Vec::new();
-        self.forces
-            .iter()
-            .filter(|(_, value)| value.finished() && value.decay_type == ForceDecayType::Automatic)
-            .for_each(|(key, _)| dead_keys.push(*key));
-
-        dead_keys.iter().for_each(|key| {
-            self.forces.remove(key);
-        });
+        let dead_keys = self
+            .sorted_ids(|force| force.finished() && force.decay_type == ForceDecayType::Automatic);
+        for key in dead_keys {
+            self.take(key, ForceChangeReason::Expired);
+        }
     }
 
     fn remove_scheduled_forces(&mut self) {
-        let mut finished_keys: Vec<ForceId> = Vec::new();
+        let scheduled = self.sorted_ids(|force| {
+            force.finished() && force.decay_type == ForceDecayType::Manual && force.pending_removal
+        });
+        for key in scheduled {
+            self.take(key, ForceChangeReason::Removed);
+        }
+    }
 
-        self.forces
+    /// Ids of the forces matching `filter`, in key order so changes are logged deterministically
+    fn sorted_ids(&self, filter: impl Fn(&Force) -> bool) -> Vec<ForceId> {
+        let mut ids: Vec<ForceId> = self
+            .forces
             .iter()
-            .filter(|(_, value)| value.finished() && value.decay_type == ForceDecayType::Manual)
-            .for_each(|(key, _)| finished_keys.push(*key));
-
-        finished_keys.iter().for_each(|key| {
-            if self.scheduled_to_remove.contains(key) {
-                self.forces.remove(key);
-                self.scheduled_to_remove.remove(key);
-            }
-        });
+            .filter(|(_, force)| filter(force))
+            .map(|(force_id, _)| *force_id)
+            .collect();
+        ids.sort();
+        ids
     }
 
+    /// The sum of every force after overrides and masks, clamped to the max combined length
     pub fn get_combined_force(&self) -> Vec3 {
-        self.forces.values().map(|force| force.applied_force).sum()
+        let combined = self.sum();
+        match self.max_combined {
+            Some(max) => combined.clamp_length_max(max),
+            None => combined,
+        }
+    }
+
+    /// Whether the max combined length is cutting the combined force short
+    pub fn is_clamped(&self) -> bool {
+        self.max_combined
+            .is_some_and(|max| self.sum().length() > max)
+    }
+
+    fn sum(&self) -> Vec3 {
+        // Float addition isn't associative and map order changes between processes, so sum in key order
+        let mut force_ids: Vec<&ForceId> = self.forces.keys().collect();
+        force_ids.sort();
+        force_ids
+            .into_iter()
+            .filter(|force_id| !self.is_overridden(**force_id))
+            .map(|force_id| self.masked(*force_id, self.forces[force_id].current()))
+            .sum()
+    }
+
+    fn is_overridden(&self, force_id: ForceId) -> bool {
+        self.rules.overrides.iter().any(|(by, overridden)| {
+            *by != force_id && self.has_key(*by) && overridden.contains(&force_id)
+        })
+    }
+
+    fn masked(&self, force_id: ForceId, force: Vec3) -> Vec3 {
+        self.rules
+            .masks
+            .iter()
+            .filter(|(by, _)| *by != force_id && self.has_key(*by))
+            .fold(force, |force, (_, keep)| {
+                Vec3::select(*keep, force, Vec3::ZERO)
+            })
     }
 
     pub fn tick(&mut self, delta: std::time::Duration) {
@@ -398,13 +1148,204 @@ impl Forces {
     }
 }
 
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
+/// How a wind zone weakens away from its origin
+#[derive(Clone, Copy, PartialEq, Debug, Default, Reflect)]
+pub enum WindFalloff {
+    /// Full strength anywhere in the zone
+    #[default]
+    Uniform,
+    /// Fades out linearly to nothing at `radius` from the origin. No wind at all if `radius` isn't
+    /// positive.
+    Radial { radius: f32 },
+    /// Fades out linearly to nothing `length` downwind of the origin, like a fan. No wind at all
+    /// if `length` isn't positive.
+    Fan { length: f32 },
+}
+
+impl WindFalloff {
+    /// The strength scale at `offset` from the zone's origin, for wind blowing along `direction`
+    pub fn scale(&self, offset: Vec3, direction: Vec3) -> f32 {
+        match *self {
+            WindFalloff::Uniform => 1.0,
+            WindFalloff::Radial { radius } if radius > 0.0 => {
+                (1.0 - offset.length() / radius).clamp(0.0, 1.0)
+            }
+            WindFalloff::Fan { length } if length > 0.0 => {
+                (1.0 - offset.dot(direction) / length).clamp(0.0, 1.0)
+            }
+            WindFalloff::Radial { .. } | WindFalloff::Fan { .. } => 0.0,
+        }
+    }
+}
+
+/// Blows anything with `Forces` inside the sensor collider on the same entity. Overlapping zones
+/// add up. The direction and falloff are in the zone's own frame, so rotating or scaling its
+/// entity turns and stretches the wind along with the collider.
+#[derive(Component, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct WindZone {
+    direction: Vec3,
+    pub strength: f32,
+    /// How far the strength wavers, as a fraction of it
+    pub turbulence: f32,
+    pub falloff: WindFalloff,
+    /// Multiplier on the wind while the entity is off the ground
+    pub airborne_scale: f32,
+}
+
+impl Default for WindZone {
+    fn default() -> Self {
+        WindZone::new(Vec3::X, 5.0)
+    }
+}
+
+impl WindZone {
+    pub fn new(direction: Vec3, strength: f32) -> Self {
+        WindZone {
+            direction: direction.normalize_or_zero(),
+            strength,
+            turbulence: 0.0,
+            falloff: WindFalloff::Uniform,
+            airborne_scale: 1.0,
+        }
+    }
+
+    pub fn with_turbulence(mut self, turbulence: f32) -> Self {
+        self.turbulence = turbulence;
+        self
+    }
+
+    pub fn with_falloff(mut self, falloff: WindFalloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    pub fn with_airborne_scale(mut self, scale: f32) -> Self {
+        self.airborne_scale = scale;
+        self
+    }
+
+    pub fn direction(&self) -> Vec3 {
+        self.direction
+    }
+
+    /// The wind `offset` from the zone's origin at `elapsed` seconds, both in the zone's own frame
+    pub fn force_at(&self, offset: Vec3, elapsed: f32, airborne: bool) -> Vec3 {
+        // Two detuned waves, cheap and the same on every run
+        let noise = (elapsed * 1.7 + offset.x * 0.9).sin() * (elapsed * 2.3 + offset.z * 1.3).cos();
+        let gust = 1.0 + self.turbulence * noise;
+        let airborne_scale = if airborne { self.airborne_scale } else { 1.0 };
+        self.direction
+            * self.strength
+            * self.falloff.scale(offset, self.direction)
+            * gust
+            * airborne_scale
+    }
+}
+
+/// How a collider's surface changes running on it. Every value is a multiplier, 1.0 everywhere
+/// is plain ground.
+#[derive(Component, Clone, Copy, PartialEq, Debug, Reflect)]
+#[reflect(Component)]
+pub struct SurfaceMaterial {
+    /// How quickly the run builds up to top speed
+    pub acceleration: f32,
+    pub max_speed: f32,
+    /// Strength and length of the skid left when letting go of the stick
+    pub skid: f32,
+    /// How quickly the character turns to face a new direction
+    pub turn_rate: f32,
+}
+
+impl SurfaceMaterial {
+    pub const DEFAULT: SurfaceMaterial = SurfaceMaterial {
+        acceleration: 1.0,
+        max_speed: 1.0,
+        skid: 1.0,
+        turn_rate: 1.0,
+    };
+    pub const ICE: SurfaceMaterial = SurfaceMaterial {
+        acceleration: 0.3,
+        max_speed: 1.1,
+        skid: 3.0,
+        turn_rate: 0.25,
+    };
+    pub const SAND: SurfaceMaterial = SurfaceMaterial {
+        acceleration: 0.6,
+        max_speed: 0.6,
+        skid: 0.5,
+        turn_rate: 0.8,
+    };
+    pub const SNOW: SurfaceMaterial = SurfaceMaterial {
+        acceleration: 0.7,
+        max_speed: 0.8,
+        skid: 1.5,
+        turn_rate: 0.6,
+    };
+    pub const METAL: SurfaceMaterial = SurfaceMaterial {
+        acceleration: 1.2,
+        max_speed: 1.05,
+        skid: 1.2,
+        turn_rate: 1.0,
+    };
+}
+
+impl Default for SurfaceMaterial {
+    fn default() -> Self {
+        SurfaceMaterial::DEFAULT
+    }
+}
+
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
 pub enum GroundedState {
     #[default]
     Grounded,
+    /// Just left the ground without jumping. Airborne in every way except that a jump is still
+    /// allowed until the coyote window runs out.
+    Coyote,
     Airborne,
 }
 
+/// How a character copes with slopes
+#[derive(Clone, Copy, Reflect)]
+pub struct SlopeTuning {
+    /// Degrees. Anything steeper can't be stood on and slides the character off.
+    pub max_walkable_angle: f32,
+    /// How quickly a steep slope speeds up the slide down it
+    pub slide_acceleration: f32,
+    /// Multiplier on the jump force when jumping off a steep slope
+    pub steep_jump_scale: f32,
+    /// Run speed lost per unit of climb, 0.5 halves the speed running straight up a wall
+    pub uphill_slowdown: f32,
+    /// Run speed gained per unit of descent
+    pub downhill_speedup: f32,
+}
+
+impl Default for SlopeTuning {
+    fn default() -> Self {
+        SlopeTuning {
+            max_walkable_angle: 45.0,
+            slide_acceleration: 20.0,
+            steep_jump_scale: 0.4,
+            uphill_slowdown: 0.5,
+            downhill_speedup: 0.3,
+        }
+    }
+}
+
+/// What the ground probe found under the character
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GroundHit {
+    pub entity: Entity,
+    pub point: Vec3,
+    pub normal: Vec3,
+    /// From the bottom of the sensor shape down to the ground
+    pub distance: f32,
+}
+
+/// How far below the sensor shape the ground can be and still count as stood on
+pub(crate) const GROUND_CONTACT_DISTANCE: f32 = 0.3;
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct GroundSensor {
@@ -412,24 +1353,157 @@ pub struct GroundSensor {
     shape: bevy_rapier3d::prelude::Collider,
     #[reflect(ignore)]
     state: GroundedState,
+    /// The material of the ground last hit, plain ground if it had none
+    surface: SurfaceMaterial,
     surface_normal: Vec3,
     slope_angle: f32,
     slope_gradient: Vec3,
+    /// Runs from leaving the ground, a jump is allowed until it finishes
+    coyote_timer: Timer,
+    /// How far down to look for ground while airborne
+    probe_distance: f32,
+    #[reflect(ignore)]
+    hit: Option<GroundHit>,
+    /// Time since last landing or leaving the ground
+    state_time: Stopwatch,
+    slopes: SlopeTuning,
+    /// Standing on a moving platform as of the last tick
+    riding_platform: bool,
+    /// How far the ground turned the character about Y this tick, in radians
+    platform_turn: f32,
 }
 
 impl GroundSensor {
+    pub fn with_slopes(mut self, tuning: SlopeTuning) -> Self {
+        self.slopes = tuning;
+        self
+    }
+
+    pub fn slopes(&self) -> &SlopeTuning {
+        &self.slopes
+    }
+
+    /// Standing on ground too steep to walk on
+    pub fn on_steep_slope(&self) -> bool {
+        self.grounded() && self.slope_angle > self.slopes.max_walkable_angle
+    }
+
+    /// Turns a heading into one along the ground, sped up downhill and slowed down uphill
+    pub fn along_ground(&self, heading: Vec3) -> Vec3 {
+        let along = heading
+            .reject_from_normalized(self.surface_normal)
+            .normalize_or_zero();
+        let scale = if along.y > 0.0 {
+            1.0 - along.y * self.slopes.uphill_slowdown
+        } else {
+            1.0 - along.y * self.slopes.downhill_speedup
+        };
+        along * heading.length() * scale.max(0.0)
+    }
+
+    pub fn with_probe_distance(mut self, distance: f32) -> Self {
+        self.probe_distance = distance;
+        self
+    }
+
+    pub fn probe_distance(&self) -> f32 {
+        self.probe_distance
+    }
+
+    /// The ground below the character within the probe distance, whether touching it or not
+    pub fn ground_hit(&self) -> Option<&GroundHit> {
+        self.hit.as_ref()
+    }
+
+    pub fn set_ground_hit(&mut self, hit: Option<GroundHit>) {
+        self.hit = hit;
+    }
+
+    pub fn distance_to_ground(&self) -> Option<f32> {
+        self.hit.map(|hit| hit.distance)
+    }
+
+    /// Seconds since landing, 0 while not grounded
+    pub fn grounded_for(&self) -> f32 {
+        if self.grounded() {
+            self.state_time.elapsed_secs()
+        } else {
+            0.0
+        }
+    }
+
+    /// Seconds since leaving the ground, including the coyote window. 0 while grounded.
+    pub fn airborne_for(&self) -> f32 {
+        if self.grounded() {
+            0.0
+        } else {
+            self.state_time.elapsed_secs()
+        }
+    }
+
+    pub fn with_coyote_time(mut self, seconds: f32) -> Self {
+        self.coyote_timer = Timer::from_seconds(seconds, TimerMode::Once);
+        self
+    }
+
+    /// Actually standing on something, false during the coyote window
     pub fn grounded(&self) -> bool {
         self.state == GroundedState::Grounded
     }
 
+    pub fn in_coyote_time(&self) -> bool {
+        self.state == GroundedState::Coyote
+    }
+
+    /// Grounded or still within the coyote window
+    pub fn can_jump(&self) -> bool {
+        self.grounded() || self.in_coyote_time()
+    }
+
+    pub fn state(&self) -> GroundedState {
+        self.state
+    }
+
     pub fn set_state(&mut self, state: GroundedState) {
+        if state == GroundedState::Coyote && self.state != GroundedState::Coyote {
+            self.coyote_timer.reset();
+        }
+        if (state == GroundedState::Grounded) != self.grounded() {
+            self.state_time.reset();
+        }
         self.state = state;
     }
 
+    pub fn tick_state_time(&mut self, delta: std::time::Duration) {
+        self.state_time.tick(delta);
+    }
+
+    /// Ticks the coyote window, ending it once it runs out
+    pub fn tick_coyote_time(&mut self, delta: std::time::Duration) {
+        if self.state == GroundedState::Coyote && self.coyote_timer.tick(delta).finished() {
+            self.state = GroundedState::Airborne;
+        }
+    }
+
     pub fn shape_ref(&self) -> &bevy_rapier3d::prelude::Collider {
         &self.shape
     }
 
+    /// The collider under the character, if it's close enough to stand on
+    pub fn ground_entity(&self) -> Option<Entity> {
+        self.hit
+            .filter(|hit| hit.distance <= GROUND_CONTACT_DISTANCE)
+            .map(|hit| hit.entity)
+    }
+
+    pub fn surface(&self) -> &SurfaceMaterial {
+        &self.surface
+    }
+
+    pub fn set_surface(&mut self, surface: SurfaceMaterial) {
+        self.surface = surface;
+    }
+
     pub fn set_normal(&mut self, normal: Vec3) {
         if normal != self.surface_normal {
             self.surface_normal = normal;
@@ -449,6 +1523,23 @@ impl GroundSensor {
     pub fn get_slope_gradient(&self) -> Vec3 {
         self.slope_gradient
     }
+
+    pub fn riding_platform(&self) -> bool {
+        self.riding_platform
+    }
+
+    pub fn set_riding_platform(&mut self, riding: bool) {
+        self.riding_platform = riding;
+    }
+
+    /// How far a rotating platform underfoot turned the character about Y this tick, in radians
+    pub fn platform_turn(&self) -> f32 {
+        self.platform_turn
+    }
+
+    pub fn set_platform_turn(&mut self, radians: f32) {
+        self.platform_turn = radians;
+    }
 }
 
 impl Default for GroundSensor {
@@ -456,9 +1547,402 @@ impl Default for GroundSensor {
         GroundSensor {
             shape: bevy_rapier3d::prelude::Collider::cuboid(0.25, 0.1, 0.25),
             state: GroundedState::default(),
+            surface: SurfaceMaterial::DEFAULT,
             surface_normal: Vec3::Y,
             slope_angle: 0.0,
             slope_gradient: Vec3::Z,
+            coyote_timer: Timer::from_seconds(0.1, TimerMode::Once),
+            probe_distance: 20.0,
+            hit: None,
+            state_time: Stopwatch::new(),
+            slopes: SlopeTuning::default(),
+            riding_platform: false,
+            platform_turn: 0.0,
+        }
+    }
+}
+
+/// A near-vertical surface the character ran into while airborne
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WallContact {
+    pub wall: Entity,
+    pub normal: Vec3,
+}
+
+/// Tuning for the wall kick, started by pressing Jump shortly after running into a wall
+#[derive(Clone, Copy)]
+pub struct WallKickTuning {
+    /// Seconds after touching a wall during which a kick is still accepted
+    pub window: f32,
+    pub jump_force: f32,
+    /// The kick never sends the character away slower than this
+    pub min_speed: f32,
+    /// Largest `normal.y` a surface can have and still count as a wall
+    pub max_normal_y: f32,
+}
+
+impl Default for WallKickTuning {
+    fn default() -> Self {
+        WallKickTuning {
+            window: 0.1,
+            jump_force: 12.0,
+            min_speed: 6.0,
+            max_normal_y: 0.3,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct WallSensor {
+    contact: Option<WallContact>,
+    window: Timer,
+    kicked_wall: Option<Entity>,
+    tuning: WallKickTuning,
+}
+
+impl WallSensor {
+    pub fn with_wall_kick(mut self, tuning: WallKickTuning) -> Self {
+        self.window = Timer::from_seconds(tuning.window, TimerMode::Once);
+        self.tuning = tuning;
+        self
+    }
+
+    pub fn tuning(&self) -> &WallKickTuning {
+        &self.tuning
+    }
+
+    /// The wall touched within the kick window, if any
+    pub fn contact(&self) -> Option<WallContact> {
+        self.contact
+    }
+
+    /// The contact, unless that wall was the last one kicked since landing
+    pub fn kickable(&self) -> Option<WallContact> {
+        self.contact
+            .filter(|contact| Some(contact.wall) != self.kicked_wall)
+    }
+
+    pub fn kicked_wall(&self) -> Option<Entity> {
+        self.kicked_wall
+    }
+
+    pub fn touch(&mut self, contact: WallContact) {
+        self.contact = Some(contact);
+        self.window.reset();
+    }
+
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        if self.window.tick(delta).finished() {
+            self.contact = None;
+        }
+    }
+
+    pub fn kick(&mut self, wall: Entity) {
+        self.kicked_wall = Some(wall);
+        self.contact = None;
+    }
+
+    pub fn land(&mut self) {
+        self.kicked_wall = None;
+        self.contact = None;
+    }
+}
+
+impl Default for WallSensor {
+    fn default() -> Self {
+        let tuning = WallKickTuning::default();
+        WallSensor {
+            contact: None,
+            window: Timer::from_seconds(tuning.window, TimerMode::Once),
+            kicked_wall: None,
+            tuning,
+        }
+    }
+}
+
+/// Tuning for the ground pound, started with Crouch while airborne
+#[derive(Clone, Copy)]
+pub struct GroundPoundTuning {
+    /// Seconds spent frozen in mid-air before dropping
+    pub hover_time: f32,
+    pub fall_speed: f32,
+    /// Seconds after landing during which Jump is ignored
+    pub recovery_time: f32,
+}
+
+impl Default for GroundPoundTuning {
+    fn default() -> Self {
+        GroundPoundTuning {
+            hover_time: 0.2,
+            fall_speed: 30.0,
+            recovery_time: 0.3,
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GroundPoundPhase {
+    #[default]
+    Idle,
+    Hovering,
+    Falling,
+    Recovering,
+}
+
+#[derive(Component)]
+pub struct GroundPounder {
+    phase: GroundPoundPhase,
+    timer: Timer,
+    tuning: GroundPoundTuning,
+}
+
+impl GroundPounder {
+    pub fn with_ground_pound(mut self, tuning: GroundPoundTuning) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
+    pub fn tuning(&self) -> &GroundPoundTuning {
+        &self.tuning
+    }
+
+    pub fn phase(&self) -> GroundPoundPhase {
+        self.phase
+    }
+
+    /// Hovering or falling, i.e. the pound owns the character's movement
+    pub fn is_pounding(&self) -> bool {
+        matches!(
+            self.phase,
+            GroundPoundPhase::Hovering | GroundPoundPhase::Falling
+        )
+    }
+
+    pub fn locks_jump(&self) -> bool {
+        self.phase != GroundPoundPhase::Idle
+    }
+
+    pub fn start(&mut self) {
+        self.phase = GroundPoundPhase::Hovering;
+        self.timer = Timer::from_seconds(self.tuning.hover_time, TimerMode::Once);
+    }
+
+    pub fn land(&mut self) {
+        self.phase = GroundPoundPhase::Recovering;
+        self.timer = Timer::from_seconds(self.tuning.recovery_time, TimerMode::Once);
+    }
+
+    /// Moves on from hovering to falling and from recovering to idle as the timer runs out
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        if !self.timer.tick(delta).finished() {
+            return;
+        }
+        self.phase = match self.phase {
+            GroundPoundPhase::Hovering => GroundPoundPhase::Falling,
+            GroundPoundPhase::Recovering => GroundPoundPhase::Idle,
+            phase => phase,
+        };
+    }
+}
+
+impl Default for GroundPounder {
+    fn default() -> Self {
+        GroundPounder {
+            phase: GroundPoundPhase::Idle,
+            timer: Timer::default(),
+            tuning: GroundPoundTuning::default(),
+        }
+    }
+}
+
+/// Sent when a ground pound hits the ground
+#[derive(Event, Clone, Debug)]
+pub struct GroundPoundLanded {
+    pub entity: Entity,
+    pub position: Vec3,
+    pub fall_speed: f32,
+}
+
+/// Tuning for the dive and the belly slide it lands in
+#[derive(Clone, Copy)]
+pub struct DiveTuning {
+    /// The dive never sends the character forward slower than this
+    pub speed: f32,
+    /// Upward force of a dive started on the ground, so it leaves the ground before sliding
+    pub hop_force: f32,
+    pub roll_out_force: f32,
+}
+
+impl Default for DiveTuning {
+    fn default() -> Self {
+        DiveTuning {
+            speed: 12.0,
+            hop_force: 6.0,
+            roll_out_force: 8.0,
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DiveState {
+    #[default]
+    Idle,
+    Diving,
+    BellySliding,
+    RollingOut,
+}
+
+#[derive(Component, Default)]
+pub struct Diver {
+    state: DiveState,
+    tuning: DiveTuning,
+}
+
+impl Diver {
+    pub fn with_dive(mut self, tuning: DiveTuning) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
+    pub fn tuning(&self) -> &DiveTuning {
+        &self.tuning
+    }
+
+    pub fn state(&self) -> DiveState {
+        self.state
+    }
+
+    pub fn start_dive(&mut self) {
+        self.state = DiveState::Diving;
+    }
+
+    pub fn start_belly_slide(&mut self) {
+        self.state = DiveState::BellySliding;
+    }
+
+    pub fn roll_out(&mut self) {
+        self.state = DiveState::RollingOut;
+    }
+
+    pub fn stand_up(&mut self) {
+        self.state = DiveState::Idle;
+    }
+}
+
+/// Tuning for grabbing ledges while falling past them, all distances in units
+#[derive(Clone, Copy, Reflect)]
+pub struct LedgeTuning {
+    /// How far in front of the chest a wall is looked for
+    pub reach: f32,
+    /// How far above the chest a ledge can be and still get grabbed
+    pub grab_height: f32,
+    /// How far below the ledge the character's center hangs
+    pub hang_depth: f32,
+    /// Distance from the wall to the character's center while hanging
+    pub wall_gap: f32,
+    pub shimmy_speed: f32,
+    /// How far past the edge the character stands after climbing up
+    pub climb_inset: f32,
+    /// How far above the top of the ledge the character's center ends up after climbing, half
+    /// its height plus a little air so it lands on the top
+    pub stand_height: f32,
+    /// Seconds after letting go during which no ledge is grabbed
+    pub regrab_delay: f32,
+}
+
+impl Default for LedgeTuning {
+    fn default() -> Self {
+        LedgeTuning {
+            reach: 0.8,
+            grab_height: 0.6,
+            hang_depth: 0.9,
+            wall_gap: 0.55,
+            shimmy_speed: 3.0,
+            climb_inset: 0.6,
+            stand_height: 1.05,
+            regrab_delay: 0.4,
+        }
+    }
+}
+
+/// The top edge of a wall, found by the ledge sensor
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct Ledge {
+    pub wall: Entity,
+    /// On the face of the wall, level with its top
+    pub point: Vec3,
+    /// Horizontal, pointing out of the wall
+    pub normal: Vec3,
+}
+
+impl Ledge {
+    pub fn hang_position(&self, tuning: &LedgeTuning) -> Vec3 {
+        self.point + self.normal * tuning.wall_gap - Vec3::Y * tuning.hang_depth
+    }
+
+    pub fn climb_position(&self, tuning: &LedgeTuning) -> Vec3 {
+        self.point - self.normal * tuning.climb_inset + Vec3::Y * tuning.stand_height
+    }
+
+    /// Along the edge, to the right when facing the wall
+    pub fn tangent(&self) -> Vec3 {
+        self.normal.cross(Vec3::Y)
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct LedgeGrabber {
+    ledge: Option<Ledge>,
+    regrab: Timer,
+    tuning: LedgeTuning,
+}
+
+impl LedgeGrabber {
+    pub fn with_ledge_grab(mut self, tuning: LedgeTuning) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
+    pub fn tuning(&self) -> &LedgeTuning {
+        &self.tuning
+    }
+
+    /// The ledge being hung from
+    pub fn ledge(&self) -> Option<Ledge> {
+        self.ledge
+    }
+
+    pub fn is_hanging(&self) -> bool {
+        self.ledge.is_some()
+    }
+
+    pub fn can_grab(&self) -> bool {
+        self.regrab.finished()
+    }
+
+    pub fn grab(&mut self, ledge: Ledge) {
+        self.ledge = Some(ledge);
+    }
+
+    pub fn let_go(&mut self) {
+        self.ledge = None;
+        self.regrab = Timer::from_seconds(self.tuning.regrab_delay, TimerMode::Once);
+    }
+
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        self.regrab.tick(delta);
+    }
+}
+
+impl Default for LedgeGrabber {
+    fn default() -> Self {
+        let mut regrab = Timer::default();
+        regrab.tick(std::time::Duration::ZERO);
+        LedgeGrabber {
+            ledge: None,
+            regrab,
+            tuning: LedgeTuning::default(),
         }
     }
 }
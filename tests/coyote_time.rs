@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Runs off the far edge of a ledge and stops on the first tick past it
+fn run_off_ledge() -> Simulation {
+    let mut sim = Simulation::default();
+    sim.spawn_box(
+        Vec3::new(5.0, 0.25, 5.0),
+        Transform::from_xyz(0.0, -2.0, 2.0),
+    );
+    sim.spawn_box(
+        Vec3::new(50.0, 0.25, 50.0),
+        Transform::from_xyz(0.0, -20.0, 0.0),
+    );
+    sim.place_character(Vec3::new(0.0, -0.75, 0.0));
+    sim.step(30);
+    sim.set_move_axis(Vec2::Y);
+    sim.step_until(240, |sim| !sim.ground_sensor().grounded())
+        .expect("never left the ledge");
+    sim
+}
+
+#[test]
+fn leaving_a_ledge_starts_the_coyote_window() {
+    let mut sim = run_off_ledge();
+    let sensor = sim.ground_sensor();
+    assert_eq!(sensor.state(), GroundedState::Coyote);
+    assert!(sensor.in_coyote_time() && sensor.can_jump());
+
+    // Already falling and past running, just allowed to jump
+    sim.step(1);
+    assert!(sim.forces().has_key(ForceId::Gravity));
+    sim.step(10);
+    assert_eq!(sim.ground_sensor().state(), GroundedState::Airborne);
+    assert!(!sim.ground_sensor().can_jump());
+}
+
+#[test]
+fn a_late_jump_still_counts() {
+    let mut sim = run_off_ledge();
+    sim.step(3);
+    let before = sim.transform().translation.y;
+    sim.press(PlayerAction::Jump);
+    sim.step(10);
+
+    assert!(sim.transform().translation.y > before, "didn't jump");
+    assert_eq!(sim.ground_sensor().state(), GroundedState::Airborne);
+}
+
+#[test]
+fn jumping_after_the_window_does_nothing() {
+    let mut sim = run_off_ledge();
+    sim.step(12);
+    sim.press(PlayerAction::Jump);
+    sim.step(3);
+
+    assert!(!sim.forces().has_key(ForceId::Jump));
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+struct Cannon;
+struct Conveyor;
+
+mod other_crate {
+    pub struct Cannon;
+}
+
+fn forces_mut(sim: &mut Simulation) -> Mut<'_, Forces> {
+    let character = sim.character();
+    sim.world_mut().get_mut::<Forces>(character).unwrap()
+}
+
+#[test]
+fn custom_ids_are_distinct_per_type() {
+    assert_eq!(ForceId::custom::<Cannon>(), ForceId::custom::<Cannon>());
+    assert_ne!(ForceId::custom::<Cannon>(), ForceId::custom::<Conveyor>());
+    assert_ne!(
+        ForceId::custom::<Cannon>(),
+        ForceId::custom::<other_crate::Cannon>()
+    );
+    assert_ne!(ForceId::custom::<Cannon>(), ForceId::Run);
+}
+
+#[test]
+fn custom_forces_move_the_character() {
+    let mut sim = Simulation::on_flat_ground();
+    let start = sim.transform().translation;
+    forces_mut(&mut sim).add(
+        ForceId::custom::<Conveyor>(),
+        Force::new(Vec3::X * 3.0, None, ForceDecayType::Manual),
+    );
+    sim.step(60);
+
+    let moved = sim.transform().translation - start;
+    assert!((moved.x - 3.0).abs() < 0.1, "{moved}");
+    assert_eq!(
+        sim.forces().get_vector(ForceId::custom::<Conveyor>()),
+        Some(Vec3::X * 3.0)
+    );
+}
+
+#[test]
+fn custom_forces_sit_alongside_built_in_ones() {
+    let mut sim = Simulation::on_flat_ground();
+    let mut forces = forces_mut(&mut sim);
+    forces.add(
+        ForceId::custom::<Cannon>(),
+        Force::new(Vec3::Z, None, ForceDecayType::Manual),
+    );
+    forces.add(
+        ForceId::custom::<other_crate::Cannon>(),
+        Force::new(Vec3::X, None, ForceDecayType::Manual),
+    );
+    forces.add(
+        ForceId::Wind,
+        Force::new(Vec3::Y, None, ForceDecayType::Manual),
+    );
+    assert_eq!(forces.get_combined_force(), Vec3::ONE);
+
+    forces.remove(ForceId::custom::<Cannon>());
+    assert!(!forces.has_key(ForceId::custom::<Cannon>()));
+    assert!(forces.has_key(ForceId::custom::<other_crate::Cannon>()));
+    assert!(forces.has_key(ForceId::Wind));
+}
@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Runs forward, dives, and stops on the first tick of the belly slide
+fn belly_sliding() -> Simulation {
+    let mut sim = Simulation::on_flat_ground();
+    sim.set_move_axis(Vec2::Y);
+    sim.step(40);
+    sim.press(PlayerAction::Interact);
+    sim.step(1);
+    assert_eq!(sim.component::<Diver>().state(), DiveState::Diving);
+    assert!(sim.forces().has_key(ForceId::Dive));
+    sim.release(PlayerAction::Interact);
+    sim.set_move_axis(Vec2::ZERO);
+
+    sim.step_until(120, |sim| {
+        sim.component::<Diver>().state() == DiveState::BellySliding
+    })
+    .expect("never landed the dive");
+    sim
+}
+
+#[test]
+fn dive_lands_in_a_belly_slide_that_wears_off() {
+    let mut sim = belly_sliding();
+    assert!(sim.world().get::<Sliding>(sim.character()).is_some());
+    assert!(sim.forces().has_key(ForceId::Slide));
+
+    let start = sim.transform().translation;
+    sim.step_until(240, |sim| {
+        sim.component::<Diver>().state() != DiveState::BellySliding
+    })
+    .expect("the belly slide never ended");
+    assert_eq!(sim.component::<Diver>().state(), DiveState::Idle);
+    assert!(sim.world().get::<Sliding>(sim.character()).is_none());
+    assert!((sim.transform().translation - start).xz().length() > 2.0);
+}
+
+#[test]
+fn jump_rolls_out_of_the_slide() {
+    let mut sim = belly_sliding();
+    sim.press(PlayerAction::Jump);
+    sim.step(1);
+
+    assert_eq!(sim.component::<Diver>().state(), DiveState::RollingOut);
+    assert_eq!(sim.component::<Jumper>().kind(), Some(JumpKind::RollOut));
+    assert!(!sim.forces().has_key(ForceId::Slide));
+    assert!(sim.forces().has_key(ForceId::Jump));
+
+    sim.release(PlayerAction::Jump);
+    sim.step(90);
+    assert_eq!(sim.component::<Diver>().state(), DiveState::Idle);
+    assert!(!sim.forces().has_key(ForceId::Dive));
+}
+
+#[test]
+fn sliding_into_a_wall_stands_back_up() {
+    let mut sim = belly_sliding();
+    let ahead = sim.transform().translation.z - 1.5;
+    sim.spawn_box(
+        Vec3::new(5.0, 3.0, 0.25),
+        Transform::from_xyz(0.0, 0.0, ahead - 0.25),
+    );
+
+    let ticks = sim
+        .step_until(240, |sim| {
+            sim.component::<Diver>().state() != DiveState::BellySliding
+        })
+        .expect("never stood back up");
+    // Friction alone would take far longer to stop the slide
+    assert!(ticks < 20, "{ticks}");
+    assert!(sim.world().get::<Sliding>(sim.character()).is_none());
+}
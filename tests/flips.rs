@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Presses Jump and returns the rise and the displacement once the character is back down
+fn jump_and_land(sim: &mut Simulation) -> (f32, Vec3) {
+    let start = sim.transform().translation;
+    let mut peak = start.y;
+    sim.press(PlayerAction::Jump);
+    sim.step(2);
+    sim.step_until(240, |sim| {
+        peak = peak.max(sim.transform().translation.y);
+        sim.component::<Jumper>().kind().is_none()
+    })
+    .expect("never landed the jump");
+    sim.release(PlayerAction::Jump);
+    sim.step(1);
+    (peak - start.y, sim.transform().translation - start)
+}
+
+#[test]
+fn backflip_goes_high_and_backward() {
+    let (single_rise, _) = jump_and_land(&mut Simulation::on_flat_ground());
+
+    let mut sim = Simulation::on_flat_ground();
+    sim.press(PlayerAction::Crouch);
+    sim.step(2);
+    sim.press(PlayerAction::Jump);
+    sim.step(2);
+    assert_eq!(sim.component::<Jumper>().kind(), Some(JumpKind::Backflip));
+    let (rise, moved) = jump_and_land(&mut sim);
+
+    assert!(rise > single_rise + 1.0, "{rise} vs {single_rise}");
+    // The character faces -Z, so backward is +Z
+    assert!(moved.z > 1.0, "{moved}");
+}
+
+#[test]
+fn side_somersault_reverses_out_of_a_skid() {
+    let mut sim = Simulation::on_flat_ground();
+    sim.set_move_axis(Vec2::Y);
+    sim.step(30);
+    sim.set_move_axis(Vec2::ZERO);
+    sim.step(1);
+    assert!(sim.forces().has_key(ForceId::Skid));
+    sim.set_move_axis(Vec2::NEG_Y);
+    sim.press(PlayerAction::Jump);
+    sim.step(1);
+
+    assert_eq!(
+        sim.component::<Jumper>().kind(),
+        Some(JumpKind::SideSomersault)
+    );
+    let flip = sim
+        .forces()
+        .get_vector(ForceId::Flip)
+        .expect("no flip force");
+    assert!(flip.z > 0.0, "{flip}");
+    assert!(sim.transform().forward().z > 0.9);
+}
+
+#[test]
+fn flips_leave_the_triple_jump_chain_alone() {
+    let mut sim = Simulation::on_flat_ground();
+    jump_and_land(&mut sim);
+    assert_eq!(*sim.component::<Jumper>().stage(), JumpStage::Double);
+
+    sim.press(PlayerAction::Crouch);
+    sim.press(PlayerAction::Jump);
+    sim.step(1);
+    assert_eq!(sim.component::<Jumper>().kind(), Some(JumpKind::Backflip));
+    jump_and_land(&mut sim);
+    assert_eq!(*sim.component::<Jumper>().stage(), JumpStage::Double);
+}
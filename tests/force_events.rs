@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Steps until an event for `force_id` with `reason` shows up, returning it
+fn wait_for(
+    sim: &mut Simulation,
+    force_id: ForceId,
+    reason: ForceChangeReason,
+) -> Option<ForceChanged> {
+    let mut found = None;
+    sim.step_until(120, |sim| {
+        found = sim
+            .events::<ForceChanged>()
+            .into_iter()
+            .find(|event| event.force_id == force_id && event.reason == reason);
+        found.is_some()
+    });
+    found
+}
+
+#[test]
+fn jumping_announces_the_jump_force() {
+    let mut sim = Simulation::on_flat_ground();
+    sim.press(PlayerAction::Jump);
+    let started = wait_for(&mut sim, ForceId::Jump, ForceChangeReason::Added).unwrap();
+    assert_eq!(started.entity, sim.character());
+    assert!(started.vector.y > 0.0, "{:?}", started.vector);
+
+    sim.release(PlayerAction::Jump);
+    let ended = wait_for(&mut sim, ForceId::Jump, ForceChangeReason::Removed).unwrap();
+    assert_eq!(ended.entity, sim.character());
+    assert!(!sim.forces().has_key(ForceId::Jump));
+}
+
+#[test]
+fn skids_are_announced_until_they_expire() {
+    let mut sim = Simulation::on_flat_ground();
+    sim.set_move_axis(Vec2::Y);
+    sim.step(30);
+    sim.set_move_axis(Vec2::ZERO);
+    sim.step(1);
+    let events = sim.events::<ForceChanged>();
+    assert!(events
+        .iter()
+        .any(|event| event.force_id == ForceId::Run && event.reason == ForceChangeReason::Removed));
+    let skid = events
+        .iter()
+        .find(|event| event.force_id == ForceId::Skid)
+        .unwrap();
+    assert_eq!(skid.reason, ForceChangeReason::Added);
+
+    let expired = wait_for(&mut sim, ForceId::Skid, ForceChangeReason::Expired).unwrap();
+    assert_eq!(expired.vector, skid.vector);
+    assert!(!sim.forces().has_key(ForceId::Skid));
+}
+
+#[test]
+fn every_change_is_logged_in_order() {
+    use ForceChangeReason::*;
+
+    let mut forces = Forces::default();
+    forces.add(
+        ForceId::Run,
+        Force::new(Vec3::X, None, ForceDecayType::Manual),
+    );
+    forces.add(
+        ForceId::Run,
+        Force::new(Vec3::Z, None, ForceDecayType::Manual),
+    );
+    forces.add(
+        ForceId::Slide,
+        Force::new(Vec3::Y, None, ForceDecayType::Manual),
+    );
+    forces.add_to(ForceId::Drift, Vec3::X);
+    forces.add_to(ForceId::Drift, Vec3::X);
+    forces.remove_now(ForceId::Drift);
+
+    let changes: Vec<_> = forces.drain_changes().collect();
+    assert_eq!(
+        changes,
+        vec![
+            (ForceId::Run, Vec3::X, Added),
+            (ForceId::Run, Vec3::Z, Replaced),
+            (ForceId::Run, Vec3::Z, Evicted),
+            (ForceId::Slide, Vec3::Y, Added),
+            (ForceId::Drift, Vec3::X, Added),
+            (ForceId::Drift, Vec3::X * 2.0, Accumulated),
+            (ForceId::Drift, Vec3::X * 2.0, Removed),
+        ]
+    );
+    assert_eq!(forces.drain_changes().count(), 0);
+}
+
+#[test]
+fn unchanged_forces_stay_quiet() {
+    let mut forces = Forces::default();
+    forces.add(
+        ForceId::Gravity,
+        Force::new(Vec3::NEG_Y, None, ForceDecayType::Manual).with_max_magnitude(1.0),
+    );
+    forces.drain_changes().for_each(drop);
+
+    // Re-adding the same vector, or growing a force already at its max, changes nothing
+    for _ in 0..1000 {
+        forces.add(
+            ForceId::Gravity,
+            Force::new(Vec3::NEG_Y, None, ForceDecayType::Manual).with_max_magnitude(1.0),
+        );
+        forces.add_to(ForceId::Gravity, Vec3::NEG_Y);
+    }
+    assert_eq!(forces.drain_changes().count(), 0);
+
+    // Nobody draining the log doesn't let it grow forever
+    for step in 0..1000 {
+        forces.add_to(ForceId::Drift, Vec3::X * step as f32);
+    }
+    let changes: Vec<_> = forces.drain_changes().collect();
+    assert!(changes.len() < 1000, "{}", changes.len());
+    assert_eq!(
+        changes.last().unwrap().1,
+        forces.get_vector(ForceId::Drift).unwrap()
+    );
+}
+
+#[test]
+fn forces_leave_with_their_faded_vector() {
+    let mut forces = Forces::default();
+    forces.add(
+        ForceId::Knockback,
+        Force::new(Vec3::X * 4.0, Some(1.0), ForceDecayType::Manual)
+            .with_falloff(ForceFalloff::Linear),
+    );
+    forces.tick(std::time::Duration::from_secs_f32(0.5));
+    forces.remove_now(ForceId::Knockback);
+
+    let removed = forces.drain_changes().last().unwrap();
+    assert_eq!(removed.2, ForceChangeReason::Removed);
+    assert!(removed.1.distance(Vec3::X * 2.0) < 0.01, "{}", removed.1);
+}
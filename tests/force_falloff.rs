@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use phys64::prelude::*;
+use std::time::Duration;
+
+fn knockback(falloff: ForceFalloff) -> Forces {
+    let mut forces = Forces::default();
+    forces.add(
+        ForceId::Wind,
+        Force::new(Vec3::X * 10.0, Some(1.0), ForceDecayType::Automatic).with_falloff(falloff),
+    );
+    forces
+}
+
+fn magnitude_at(falloff: ForceFalloff, seconds: f32) -> f32 {
+    let mut forces = knockback(falloff);
+    forces.tick(Duration::from_secs_f32(seconds));
+    forces.get_combined_force().x
+}
+
+#[test]
+fn profiles_fall_off_over_the_lifespan() {
+    assert_eq!(magnitude_at(ForceFalloff::Constant, 0.5), 10.0);
+    assert!((magnitude_at(ForceFalloff::Linear, 0.0) - 10.0).abs() < 1e-4);
+    assert!((magnitude_at(ForceFalloff::Linear, 0.25) - 7.5).abs() < 1e-4);
+    assert!((magnitude_at(ForceFalloff::Linear, 0.75) - 2.5).abs() < 1e-4);
+
+    let exponential = magnitude_at(ForceFalloff::Exponential { rate: 3.0 }, 0.5);
+    assert!((exponential - 10.0 * (-1.5f32).exp()).abs() < 1e-4);
+
+    // Ease-out sheds more early on than linear does
+    let ease_out = magnitude_at(ForceFalloff::EaseOut, 0.25);
+    assert!(ease_out < 7.5 && ease_out > 0.0, "{ease_out}");
+}
+
+#[test]
+fn keyframes_are_interpolated() {
+    let curve = ForceFalloff::Keyframes(vec![1.0, 0.2, 0.6]);
+    assert_eq!(curve.sample(0.0), 1.0);
+    assert!((curve.sample(0.25) - 0.6).abs() < 1e-5);
+    assert!((curve.sample(0.5) - 0.2).abs() < 1e-5);
+    assert!((curve.sample(1.0) - 0.6).abs() < 1e-5);
+    assert_eq!(ForceFalloff::Keyframes(Vec::new()).sample(0.5), 1.0);
+
+    assert!((magnitude_at(curve, 0.75) - 4.0).abs() < 1e-4);
+}
+
+#[test]
+fn forces_without_a_lifespan_apply_in_full() {
+    let mut forces = Forces::default();
+    forces.add(
+        ForceId::Slide,
+        Force::new(Vec3::Z * 4.0, None, ForceDecayType::Manual).with_falloff(ForceFalloff::Linear),
+    );
+    forces.tick(Duration::from_secs(5));
+    assert_eq!(forces.get_vector(ForceId::Slide), Some(Vec3::Z * 4.0));
+    assert_eq!(forces.get_combined_force(), Vec3::Z * 4.0);
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use phys64::prelude::*;
+
+fn constant(vector: Vec3) -> Force {
+    Force::new(vector, None, ForceDecayType::Manual)
+}
+
+#[test]
+fn moves_evict_the_run_and_each_other() {
+    let mut forces = Forces::default();
+    forces.add(ForceId::Run, constant(Vec3::NEG_Z * 8.0));
+    assert!(forces.add(ForceId::Slide, constant(Vec3::NEG_Z * 16.0)));
+    assert!(!forces.has_key(ForceId::Run));
+
+    // The run can't come back while a move is under way
+    assert!(!forces.add(ForceId::Run, constant(Vec3::NEG_Z * 8.0)));
+    assert!(!forces.has_key(ForceId::Run));
+    assert_eq!(forces.get_combined_force(), Vec3::NEG_Z * 16.0);
+
+    forces.add(ForceId::Dive, constant(Vec3::X * 12.0));
+    assert!(!forces.has_key(ForceId::Slide));
+    assert_eq!(forces.get_combined_force(), Vec3::X * 12.0);
+}
+
+#[test]
+fn knockback_overrides_player_forces_while_present() {
+    let mut forces = Forces::default();
+    forces.add(ForceId::Run, constant(Vec3::NEG_Z * 8.0));
+    forces.add(ForceId::Gravity, constant(Vec3::NEG_Y));
+    forces.add(ForceId::Knockback, constant(Vec3::Z * 5.0));
+
+    assert!(forces.has_key(ForceId::Run));
+    assert_eq!(forces.get_vector(ForceId::Run), Some(Vec3::NEG_Z * 8.0));
+    assert_eq!(forces.get_combined_force(), Vec3::new(0.0, -1.0, 5.0));
+
+    forces.remove(ForceId::Knockback);
+    assert_eq!(forces.get_combined_force(), Vec3::new(0.0, -1.0, -8.0));
+}
+
+#[test]
+fn ground_pound_masks_horizontal_forces() {
+    let mut forces = Forces::default();
+    forces.add(ForceId::Drift, constant(Vec3::new(2.0, 0.0, 1.0)));
+    forces.add(ForceId::Wind, constant(Vec3::new(3.0, 1.0, 0.0)));
+    forces.add(ForceId::GroundPound, constant(Vec3::NEG_Y * 30.0));
+    assert_eq!(forces.get_combined_force(), Vec3::new(0.0, -29.0, 0.0));
+
+    // Without rules everything adds up as is
+    let mut forces = Forces::default().with_rules(ForceRules::none());
+    forces.add(ForceId::Run, constant(Vec3::NEG_Z));
+    forces.add(ForceId::Slide, constant(Vec3::X));
+    forces.add(ForceId::GroundPound, constant(Vec3::NEG_Y));
+    assert_eq!(forces.get_combined_force(), Vec3::new(1.0, -1.0, -1.0));
+}
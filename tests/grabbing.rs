@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// A character standing on flat ground with a crate of `weight` just in front of it
+fn crate_in_front(weight: CarryWeight) -> (Simulation, Entity) {
+    let mut sim = Simulation::default();
+    sim.spawn_box(
+        Vec3::new(25.0, 0.25, 25.0),
+        Transform::from_xyz(0.0, -2.0, 0.0),
+    );
+    sim.place_character(Vec3::new(0.0, -0.75, 0.0));
+    let crate_entity = sim
+        .world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_xyz(0.0, -1.5, -1.2)),
+            Collider::cuboid(0.25, 0.25, 0.25),
+            RigidBody::Dynamic,
+            Carryable {
+                weight,
+                offset: Vec3::new(0.0, 0.5, -0.9),
+            },
+        ))
+        .id();
+    sim.step(30);
+    (sim, crate_entity)
+}
+
+fn interact(sim: &mut Simulation) {
+    sim.press(PlayerAction::Interact);
+    sim.step(15);
+    sim.release(PlayerAction::Interact);
+    sim.step(1);
+}
+
+#[test]
+fn picking_up_stops_blocking_the_carrier_and_slows_it() {
+    let (mut sim, crate_entity) = crate_in_front(CarryWeight::Heavy);
+    interact(&mut sim);
+
+    assert_eq!(sim.component::<Carrier>().held(), Some(crate_entity));
+    let world = sim.world();
+    assert!(world.get::<Held>(crate_entity).is_some());
+    assert!(world.get::<ColliderDisabled>(crate_entity).is_none());
+    let player_data = world.resource::<PlayerData>();
+    assert_eq!(
+        player_data.held_object_index,
+        IndexPointer::FindAt(crate_entity.index() as usize)
+    );
+
+    sim.set_move_axis(Vec2::Y);
+    sim.step(120);
+    let top_speed = sim.component::<Speed>().current();
+    assert!(top_speed <= 20.0 * CarryWeight::Heavy.speed_scale() + 0.01);
+
+    // The crate rides along at its offset instead of blocking the way
+    let carried = sim
+        .world()
+        .get::<Transform>(crate_entity)
+        .unwrap()
+        .translation;
+    let expected =
+        sim.transform().translation + sim.transform().rotation * Vec3::new(0.0, 0.5, -0.9);
+    assert!(carried.distance(expected) < 0.5, "{carried} vs {expected}");
+}
+
+#[test]
+fn throwing_sends_the_object_flying() {
+    let (mut sim, crate_entity) = crate_in_front(CarryWeight::Light);
+    interact(&mut sim);
+    let held_at = sim
+        .world()
+        .get::<Transform>(crate_entity)
+        .unwrap()
+        .translation;
+    interact(&mut sim);
+
+    assert_eq!(sim.component::<Carrier>().held(), None);
+    assert!(sim.world().get::<Held>(crate_entity).is_none());
+    assert_eq!(sim.component::<Speed>().max(), 20.0);
+
+    sim.step(1);
+    let player_data = sim.world().resource::<PlayerData>();
+    assert_eq!(player_data.held_object_index, IndexPointer::Empty);
+    assert_eq!(player_data.held_object_position, Vec3::ZERO);
+
+    sim.step(29);
+    let thrown_to = sim
+        .world()
+        .get::<Transform>(crate_entity)
+        .unwrap()
+        .translation;
+    assert!(
+        (thrown_to - held_at).xz().length() > 3.0,
+        "{held_at} -> {thrown_to}"
+    );
+}
+
+#[test]
+fn heavier_objects_fly_shorter() {
+    let distance = |weight| {
+        let (mut sim, crate_entity) = crate_in_front(weight);
+        interact(&mut sim);
+        let held_at = sim
+            .world()
+            .get::<Transform>(crate_entity)
+            .unwrap()
+            .translation;
+        interact(&mut sim);
+        sim.step(20);
+        let thrown_to = sim
+            .world()
+            .get::<Transform>(crate_entity)
+            .unwrap()
+            .translation;
+        (thrown_to - held_at).xz().length()
+    };
+    assert!(distance(CarryWeight::Heavy) < distance(CarryWeight::Light));
+}
+
+#[test]
+fn held_objects_stay_solid_to_everything_else() {
+    let (mut sim, crate_entity) = crate_in_front(CarryWeight::Light);
+    interact(&mut sim);
+    sim.step(5);
+
+    let carried = sim
+        .world()
+        .get::<Transform>(crate_entity)
+        .unwrap()
+        .translation;
+    let player = sim.character();
+    let from = carried + Vec3::X * 3.0;
+    let hit = sim
+        .world()
+        .resource::<RapierContext>()
+        .cast_ray(
+            from,
+            -Vec3::X,
+            5.0,
+            true,
+            QueryFilter::new().exclude_collider(player),
+        )
+        .map(|(entity, _)| entity);
+    assert_eq!(hit, Some(crate_entity));
+}
+
+#[test]
+fn the_carriers_own_filter_comes_back_after_a_throw() {
+    let (mut sim, crate_entity) = crate_in_front(CarryWeight::Light);
+    let character = sim.character();
+    let own_filter = CollisionGroups::new(Group::GROUP_1, Group::GROUP_1 | Group::GROUP_2);
+    sim.world_mut()
+        .get_mut::<Carrier>(character)
+        .unwrap()
+        .held_group = Group::GROUP_2;
+    sim.world_mut()
+        .get_mut::<KinematicCharacterController>(character)
+        .unwrap()
+        .filter_groups = Some(own_filter);
+
+    interact(&mut sim);
+    let groups = *sim.world().get::<CollisionGroups>(crate_entity).unwrap();
+    assert_eq!(groups.memberships, Group::GROUP_2);
+    let filter = sim
+        .component::<KinematicCharacterController>()
+        .filter_groups;
+    assert_eq!(
+        filter,
+        Some(CollisionGroups::new(Group::GROUP_1, Group::GROUP_1))
+    );
+
+    interact(&mut sim);
+    let filter = sim
+        .component::<KinematicCharacterController>()
+        .filter_groups;
+    assert_eq!(filter, Some(own_filter));
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+fn over_floor(height: f32) -> (Simulation, Entity) {
+    let mut sim = Simulation::default();
+    let floor = sim.spawn_box(
+        Vec3::new(50.0, 0.25, 50.0),
+        Transform::from_xyz(0.0, -2.0, 0.0),
+    );
+    sim.place_character(Vec3::new(0.0, height, 0.0));
+    // The floor only shows up to queries once physics has stepped
+    sim.step(2);
+    (sim, floor)
+}
+
+#[test]
+fn standing_records_the_floor_under_the_feet() {
+    let (mut sim, floor) = over_floor(-0.75);
+    sim.step(30);
+    let hit = *sim.ground_sensor().ground_hit().unwrap();
+    assert_eq!(hit.entity, floor);
+    assert!((hit.point.y - -1.75).abs() < 0.01, "{}", hit.point);
+    assert!((hit.normal - Vec3::Y).length() < 1e-3, "{}", hit.normal);
+    assert!(hit.distance < 0.3, "{}", hit.distance);
+
+    let grounded_for = sim.ground_sensor().grounded_for();
+    sim.step(30);
+    assert!((sim.ground_sensor().grounded_for() - grounded_for - 0.5).abs() < 1e-3);
+    assert_eq!(sim.ground_sensor().airborne_for(), 0.0);
+
+    let data = sim.world().resource::<PlayerData>();
+    assert_eq!(data.distance_from_floor, hit.distance);
+    assert_eq!(data.floor_normal, hit.normal);
+}
+
+#[test]
+fn falling_tracks_the_distance_left() {
+    let (mut sim, floor) = over_floor(5.0);
+    // The probe runs before the tick moves the character. The bottom of the sensor shape sits 0.9
+    // under the character's origin.
+    let height_of = |sim: &Simulation| sim.transform().translation.y - 0.9 - -1.75;
+    let mut height = height_of(&sim);
+    let mut last = f32::INFINITY;
+    sim.step_until(120, |sim| {
+        let hit = *sim.ground_sensor().ground_hit().unwrap();
+        assert_eq!(hit.entity, floor);
+        assert!(
+            (hit.distance - height).abs() < 0.01,
+            "{} vs {height}",
+            hit.distance
+        );
+        assert!(hit.distance <= last);
+        last = hit.distance;
+        height = height_of(sim);
+        sim.ground_sensor().grounded()
+    })
+    .expect("never landed");
+    assert_eq!(sim.ground_sensor().airborne_for(), 0.0);
+    assert!(last < 0.3);
+}
+
+#[test]
+fn nothing_below_reads_as_out_of_reach() {
+    let mut sim = Simulation::default();
+    sim.step(10);
+    assert!(sim.ground_sensor().ground_hit().is_none());
+    assert!((sim.ground_sensor().airborne_for() - 10.0 / 60.0).abs() < 1e-3);
+
+    let data = sim.world().resource::<PlayerData>();
+    assert_eq!(data.distance_from_floor, f32::INFINITY);
+    assert_eq!(data.floor_normal, Vec3::Y);
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Floor tilted by `tilt` degrees about X. The character runs towards -Z, so positive tilts are
+/// uphill.
+fn running_on(tilt: f32) -> Simulation {
+    let mut sim = Simulation::default();
+    sim.spawn_box(
+        Vec3::new(100.0, 0.25, 100.0),
+        Transform::from_xyz(0.0, -2.0, 0.0).with_rotation(Quat::from_rotation_x(tilt.to_radians())),
+    );
+    sim.place_character(Vec3::new(0.0, -0.5, 0.0));
+    sim.step(30);
+    sim.set_move_axis(Vec2::Y);
+    sim.step(30);
+    sim
+}
+
+fn distance_covered(tilt: f32) -> f32 {
+    let mut sim = running_on(tilt);
+    let start = sim.transform().translation;
+    sim.step(60);
+    (sim.transform().translation - start).length()
+}
+
+#[test]
+fn uphill_is_slower_and_downhill_faster() {
+    let uphill = distance_covered(15.0);
+    let flat = distance_covered(0.0);
+    let downhill = distance_covered(-15.0);
+    assert!(uphill < flat * 0.95, "{uphill} vs {flat}");
+    assert!(downhill > flat * 1.03, "{downhill} vs {flat}");
+
+    let sim = running_on(15.0);
+    let run = sim.forces().get_vector(ForceId::Run).unwrap();
+    let normal = sim.ground_sensor().get_normal();
+    assert!(run.y > 0.0 && run.dot(normal).abs() < 1e-3, "{run}");
+}
+
+#[test]
+fn running_downhill_stays_on_the_ground() {
+    let mut sim = running_on(-20.0);
+    sim.step_with(120, |sim| {
+        assert!(sim.ground_sensor().grounded(), "bounced off the slope");
+    });
+    assert!(!sim.forces().has_key(ForceId::Slope));
+}
+
+/// How far the ramp in `off_the_ramp` is tilted, in radians
+const RAMP_TILT: f32 = 0.35;
+
+/// Runs up a ramp and stops on the first tick off its top
+fn off_the_ramp() -> Simulation {
+    let mut sim = Simulation::default();
+    sim.spawn_box(
+        Vec3::new(100.0, 0.25, 100.0),
+        Transform::from_xyz(0.0, -2.0, 0.0),
+    );
+    // Rises 2.7 over 7.5 along -Z, starting at z = -2
+    sim.spawn_box(
+        Vec3::new(3.0, 0.25, 4.0),
+        Transform::from_xyz(0.0, -0.65, -6.0).with_rotation(Quat::from_rotation_x(RAMP_TILT)),
+    );
+    sim.place_character(Vec3::new(0.0, -0.75, 20.0));
+    sim.step(30);
+    sim.set_move_axis(Vec2::Y);
+
+    let mut climbed = false;
+    sim.step_until(300, |sim| {
+        climbed |= sim.transform().translation.y > 0.0;
+        climbed && !sim.ground_sensor().grounded()
+    })
+    .expect("never left the ramp");
+    sim
+}
+
+#[test]
+fn ramps_launch_the_character() {
+    let mut sim = off_the_ramp();
+    let left_at = sim.transform().translation.y;
+    let mut peak = left_at;
+    sim.step_with(20, |sim| peak = peak.max(sim.transform().translation.y));
+    assert!(
+        peak > left_at + 0.1,
+        "peaked at {peak} after leaving at {left_at}"
+    );
+}
+
+#[test]
+fn the_crest_launches_along_the_slope() {
+    let sim = off_the_ramp();
+    let ramp_normal = Quat::from_rotation_x(RAMP_TILT) * Vec3::Y;
+    assert!(
+        sim.ground_sensor()
+            .get_normal()
+            .abs_diff_eq(ramp_normal, 1e-3),
+        "{}",
+        sim.ground_sensor().get_normal()
+    );
+    // Only the first tick of gravity bends it off the slope
+    let launch = sim.momentum().normalize();
+    assert!(launch.dot(ramp_normal).abs() < 0.03, "{launch}");
+    assert!(launch.z < 0.0 && launch.y > 0.3, "{launch}");
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Jumps from flat ground and stops near the top of the arc
+fn mid_jump() -> Simulation {
+    let mut sim = Simulation::on_flat_ground();
+    sim.press(PlayerAction::Jump);
+    sim.step(12);
+    sim.release(PlayerAction::Jump);
+    sim.step(1);
+    sim
+}
+
+#[test]
+fn pound_hovers_then_drops_straight_down() {
+    let mut sim = mid_jump();
+    sim.set_move_axis(Vec2::Y);
+    sim.press(PlayerAction::Crouch);
+    sim.step(1);
+    assert_eq!(
+        sim.component::<GroundPounder>().phase(),
+        GroundPoundPhase::Hovering
+    );
+    assert!(!sim.forces().has_key(ForceId::Jump));
+
+    let hover_at = sim.transform().translation;
+    sim.step(5);
+    assert!(sim.transform().translation.distance(hover_at) < 0.01);
+
+    sim.step_until(120, |sim| {
+        sim.component::<GroundPounder>().phase() != GroundPoundPhase::Hovering
+    })
+    .expect("never stopped hovering");
+    assert_eq!(
+        sim.component::<GroundPounder>().phase(),
+        GroundPoundPhase::Falling
+    );
+    let drop_from = sim.transform().translation;
+    sim.step(1);
+    let moved = sim.transform().translation - drop_from;
+    assert!(moved.y < -0.4, "{moved}");
+    assert!(moved.xz().length() < 0.01, "{moved}");
+    assert!(!sim.forces().has_key(ForceId::Drift));
+}
+
+#[test]
+fn landing_sends_an_event_and_locks_jump() {
+    let mut sim = mid_jump();
+    sim.press(PlayerAction::Crouch);
+
+    let mut landed = Vec::new();
+    sim.step_until(120, |sim| {
+        landed.extend(sim.events::<GroundPoundLanded>());
+        !landed.is_empty()
+    })
+    .expect("never landed");
+    let event = &landed[0];
+    assert_eq!(event.entity, sim.character());
+    assert_eq!(event.fall_speed, GroundPoundTuning::default().fall_speed);
+    assert!((event.position.y - sim.transform().translation.y).abs() < 0.5);
+
+    sim.release(PlayerAction::Crouch);
+    sim.press(PlayerAction::Jump);
+    sim.step(2);
+    assert!(!sim.forces().has_key(ForceId::Jump));
+
+    sim.release(PlayerAction::Jump);
+    sim.step(30);
+    sim.press(PlayerAction::Jump);
+    sim.step(1);
+    assert!(sim.forces().has_key(ForceId::Jump));
+}
+
+#[test]
+fn crouch_pressed_before_takeoff_does_not_pound() {
+    let mut sim = mid_jump();
+    sim.step(60);
+    sim.press(PlayerAction::Crouch);
+    sim.step(2);
+    sim.press(PlayerAction::Jump);
+    sim.step(4);
+    assert_eq!(sim.component::<Jumper>().kind(), Some(JumpKind::Backflip));
+    assert_eq!(
+        sim.component::<GroundPounder>().phase(),
+        GroundPoundPhase::Idle
+    );
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+fn jump_then_run(sim: &mut Simulation) -> Vec<Vec3> {
+    let mut path = Vec::new();
+    sim.script(
+        std::iter::repeat_n(InputFrame::new().holding(PlayerAction::Jump), 20).chain(
+            std::iter::repeat_n(InputFrame::new().with_move_axis(Vec2::new(0.5, 1.0)), 60),
+        ),
+    );
+    sim.step_with(80, |sim| path.push(sim.transform().translation));
+    path
+}
+
+#[test]
+fn single_jump_peak() {
+    let mut sim = Simulation::on_flat_ground();
+    let start = sim.transform().translation.y;
+    assert!(sim.ground_sensor().grounded());
+
+    sim.press(PlayerAction::Jump);
+    let mut peak = f32::MIN;
+    sim.step_with(60, |sim| peak = peak.max(sim.transform().translation.y));
+
+    let height = peak - start;
+    assert!(
+        (2.1..2.5).contains(&height),
+        "single jump peaked at {height}"
+    );
+
+    sim.step(30);
+    assert!(sim.ground_sensor().grounded());
+}
+
+#[test]
+fn releasing_jump_early_hops_lower() {
+    let mut held = Simulation::on_flat_ground();
+    held.press(PlayerAction::Jump);
+    let mut held_peak = f32::MIN;
+    held.step_with(60, |sim| {
+        held_peak = held_peak.max(sim.transform().translation.y)
+    });
+
+    let mut tapped = Simulation::on_flat_ground();
+    tapped.script(vec![InputFrame::new().holding(PlayerAction::Jump); 3]);
+    tapped.script([InputFrame::new()]);
+    let mut tapped_peak = f32::MIN;
+    tapped.step_with(60, |sim| {
+        tapped_peak = tapped_peak.max(sim.transform().translation.y)
+    });
+
+    assert!(tapped_peak < held_peak - 0.5);
+}
+
+#[test]
+fn runs_are_reproducible() {
+    let first = jump_then_run(&mut Simulation::on_flat_ground());
+    let second = jump_then_run(&mut Simulation::on_flat_ground());
+
+    assert_eq!(first, second);
+    assert!(first.last().unwrap().z < -5.0);
+}
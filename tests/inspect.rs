@@ -0,0 +1,96 @@
+use bevy::{
+    prelude::*,
+    reflect::{ReflectMut, ReflectRef},
+};
+use phys64::{harness::Simulation, prelude::*};
+use std::any::TypeId;
+
+fn jumping() -> Simulation {
+    let mut sim = Simulation::on_flat_ground();
+    sim.press(PlayerAction::Jump);
+    sim.step(1);
+    sim
+}
+
+#[test]
+fn movement_components_are_registered() {
+    let sim = Simulation::default();
+    let registry = sim.world().resource::<AppTypeRegistry>().read();
+    for type_id in [
+        TypeId::of::<Forces>(),
+        TypeId::of::<Speed>(),
+        TypeId::of::<Jumper>(),
+        TypeId::of::<MoveDirection>(),
+        TypeId::of::<InputBuffer>(),
+    ] {
+        let registration = registry.get(type_id).expect("type is not registered");
+        assert!(
+            registration.data::<ReflectComponent>().is_some(),
+            "{} can't be reflected as a component",
+            registration.type_info().type_path()
+        );
+    }
+}
+
+#[test]
+fn every_force_shows_its_state() {
+    let sim = jumping();
+    let registry = sim.world().resource::<AppTypeRegistry>().read();
+    let reflect_forces = registry.get_type_data::<ReflectComponent>(TypeId::of::<Forces>());
+    let forces = reflect_forces
+        .unwrap()
+        .reflect(sim.world().entity(sim.character()))
+        .unwrap();
+
+    let ReflectRef::Struct(forces) = forces.reflect_ref() else {
+        panic!("Forces should reflect as a struct");
+    };
+    let ReflectRef::Map(map) = forces.field("forces").unwrap().reflect_ref() else {
+        panic!("the force map should reflect as a map");
+    };
+    let jump = map.get(&ForceId::Jump).expect("no jump force");
+    let ReflectRef::Struct(jump) = jump.reflect_ref() else {
+        panic!("Force should reflect as a struct");
+    };
+
+    let vector = jump.field("applied_force").unwrap();
+    assert_eq!(vector.downcast_ref::<Vec3>(), Some(&(Vec3::Y * 10.0)));
+    let timer = jump.field("expiration_timer").unwrap();
+    assert!(timer.downcast_ref::<Option<Timer>>().unwrap().is_some());
+    let decay = jump.field("decay_type").unwrap();
+    assert_eq!(
+        decay.downcast_ref::<ForceDecayType>(),
+        Some(&ForceDecayType::Manual)
+    );
+    let pending = jump.field("pending_removal").unwrap();
+    assert_eq!(pending.downcast_ref::<bool>(), Some(&false));
+}
+
+#[test]
+fn forces_can_be_edited_live() {
+    let mut sim = jumping();
+    let character = sim.character();
+    let registry = sim.world().resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let reflect_forces = registry
+        .get_type_data::<ReflectComponent>(TypeId::of::<Forces>())
+        .unwrap();
+
+    let mut entity = sim.world_mut().entity_mut(character);
+    let mut forces = reflect_forces.reflect_mut(&mut entity).unwrap();
+    let ReflectMut::Struct(forces) = forces.reflect_mut() else {
+        panic!("Forces should reflect as a struct");
+    };
+    let ReflectMut::Map(map) = forces.field_mut("forces").unwrap().reflect_mut() else {
+        panic!("the force map should reflect as a map");
+    };
+    let jump = map.get_mut(&ForceId::Jump).expect("no jump force");
+    let ReflectMut::Struct(jump) = jump.reflect_mut() else {
+        panic!("Force should reflect as a struct");
+    };
+    jump.field_mut("applied_force")
+        .unwrap()
+        .apply(&(Vec3::Y * 20.0));
+
+    assert_eq!(sim.forces().get_vector(ForceId::Jump), Some(Vec3::Y * 20.0));
+}
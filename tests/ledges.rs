@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Drops the character down the face of a block whose top is at y = 2, 3 units either side of
+/// x = 0, and stops once it hangs from the top
+fn hanging() -> (Simulation, Entity) {
+    let mut sim = Simulation::default();
+    sim.spawn_box(
+        Vec3::new(25.0, 0.25, 25.0),
+        Transform::from_xyz(0.0, -2.0, 0.0),
+    );
+    let block = sim.spawn_box(
+        Vec3::new(3.0, 2.0, 2.0),
+        Transform::from_xyz(0.0, 0.0, -2.6),
+    );
+    sim.place_character(Vec3::new(0.0, 4.0, 0.0));
+    sim.step_until(120, |sim| grabber(sim).is_hanging())
+        .expect("never grabbed the ledge");
+    (sim, block)
+}
+
+struct Conveyor;
+
+fn grabber(sim: &Simulation) -> &LedgeGrabber {
+    sim.component::<LedgeGrabber>()
+}
+
+#[test]
+fn falling_past_a_ledge_hangs_from_it() {
+    let (mut sim, block) = hanging();
+    let ledge = grabber(&sim).ledge().unwrap();
+    assert_eq!(ledge.wall, block);
+    assert!((ledge.point.y - 2.0).abs() < 1e-3, "{}", ledge.point);
+    assert!(ledge.normal.abs_diff_eq(Vec3::Z, 1e-3), "{}", ledge.normal);
+
+    let hang = ledge.hang_position(grabber(&sim).tuning());
+    sim.step_with(60, |sim| {
+        assert!(grabber(sim).is_hanging());
+        assert!(sim.transform().translation.abs_diff_eq(hang, 1e-3));
+        assert!(!sim.forces().has_key(ForceId::Gravity));
+        assert!(!sim.ground_sensor().grounded());
+        // Hanging still doesn't keep re-announcing the hang
+        assert!(sim.events::<ForceChanged>().is_empty());
+    });
+}
+
+#[test]
+fn custom_forces_wait_until_the_ledge_is_let_go() {
+    let (mut sim, _) = hanging();
+    let hang = sim.transform().translation;
+    let character = sim.character();
+    let conveyor = ForceId::custom::<Conveyor>();
+    let mut forces = sim.world_mut().get_mut::<Forces>(character).unwrap();
+    // Custom forces keep pushing a hanging character unless the rules hold them off too
+    forces.set_rules(ForceRules::default().overrides(ForceId::Hang, &[conveyor]));
+    forces.add(
+        conveyor,
+        Force::new(Vec3::X * 5.0, None, ForceDecayType::Manual),
+    );
+    sim.step(20);
+    assert!(sim.forces().has_key(conveyor));
+    assert!(sim.transform().translation.abs_diff_eq(hang, 1e-3));
+
+    sim.press(PlayerAction::Crouch);
+    sim.step(10);
+    assert!(!grabber(&sim).is_hanging());
+    assert!(sim.forces().has_key(conveyor));
+    let moved = sim.transform().translation.x - hang.x;
+    assert!(moved > 0.5, "{moved}");
+}
+
+#[test]
+fn jump_climbs_up_onto_the_ledge() {
+    let (mut sim, block) = hanging();
+    sim.press(PlayerAction::Jump);
+    sim.step(10);
+    assert!(!grabber(&sim).is_hanging());
+    assert!(sim.ground_sensor().grounded());
+    assert_eq!(sim.ground_sensor().ground_entity(), Some(block));
+    assert!(sim.transform().translation.z < -0.6);
+    // The press went into the climb, not a jump off the top
+    assert!(!sim.forces().has_key(ForceId::Jump));
+}
+
+#[test]
+fn crouch_lets_go_of_the_ledge() {
+    let (mut sim, _) = hanging();
+    let hang_y = sim.transform().translation.y;
+    sim.press(PlayerAction::Crouch);
+    sim.step(1);
+    assert!(!grabber(&sim).is_hanging());
+    sim.step(20);
+    assert!(!grabber(&sim).is_hanging(), "grabbed the same ledge again");
+    assert!(sim.transform().translation.y < hang_y - 1.0);
+    assert_eq!(
+        sim.component::<GroundPounder>().phase(),
+        GroundPoundPhase::Idle
+    );
+}
+
+#[test]
+fn shimmying_stops_at_the_end_of_the_ledge() {
+    let (mut sim, _) = hanging();
+    let start = sim.transform().translation;
+    sim.set_move_axis(Vec2::X);
+    sim.step(20);
+    let moved = sim.transform().translation - start;
+    assert!(moved.x > 0.5, "{moved}");
+    assert!(moved.y.abs() < 1e-3 && moved.z.abs() < 1e-3, "{moved}");
+
+    sim.step(120);
+    assert!(grabber(&sim).is_hanging());
+    let end = sim.transform().translation.x;
+    assert!(end > 2.5 && end <= 3.0, "{end}");
+    sim.step(10);
+    assert_eq!(sim.transform().translation.x, end);
+}
@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+fn running_start(ticks: u32) -> Simulation {
+    let mut sim = Simulation::on_flat_ground();
+    sim.set_move_axis(Vec2::Y);
+    sim.step(ticks);
+    sim
+}
+
+/// Returns the rise and the horizontal distance covered until touching down
+fn jump_arc(sim: &mut Simulation) -> (f32, f32) {
+    let start = sim.transform().translation;
+    let mut peak = start.y;
+    let mut left_ground = false;
+    let mut landed_at = None;
+    sim.press(PlayerAction::Jump);
+    sim.step_with(120, |sim| {
+        let translation = sim.transform().translation;
+        peak = peak.max(translation.y);
+        if !sim.ground_sensor().grounded() {
+            left_ground = true;
+        } else if left_ground && landed_at.is_none() {
+            landed_at = Some(translation);
+        }
+    });
+    let landed_at = landed_at.expect("never landed");
+
+    (peak - start.y, (landed_at - start).xz().length())
+}
+
+#[test]
+fn long_jump_is_lower_and_longer() {
+    let mut running = running_start(60);
+    let (running_rise, running_distance) = jump_arc(&mut running);
+
+    let mut long = running_start(60);
+    long.press(PlayerAction::Crouch);
+    long.step(3);
+    let (long_rise, long_distance) = jump_arc(&mut long);
+
+    assert!(long_rise < running_rise * 0.75, "rose {long_rise}");
+    assert!(
+        long_distance > running_distance * 1.5,
+        "covered {long_distance}"
+    );
+    assert_eq!(*long.component::<Jumper>().stage(), JumpStage::Single);
+}
+
+#[test]
+fn long_jump_needs_speed() {
+    let mut sim = running_start(5);
+    sim.press(PlayerAction::Crouch);
+    sim.step(1);
+    sim.press(PlayerAction::Jump);
+    sim.step(2);
+
+    assert!(!sim.forces().has_key(ForceId::LongJump));
+    assert!(sim.forces().has_key(ForceId::Jump));
+}
+
+#[test]
+fn long_jump_landing_resets_the_jump_chain() {
+    let mut sim = running_start(60);
+    sim.press(PlayerAction::Crouch);
+    sim.step(3);
+    sim.press(PlayerAction::Jump);
+    sim.step(2);
+    assert!(sim.component::<Jumper>().is_long_jumping());
+
+    sim.step_until(240, |sim| {
+        sim.ground_sensor().grounded() && !sim.component::<Jumper>().is_long_jumping()
+    })
+    .expect("never landed the long jump");
+    assert_eq!(*sim.component::<Jumper>().stage(), JumpStage::Single);
+    assert!(!sim.forces().has_key(ForceId::LongJump));
+}
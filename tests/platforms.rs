@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+fn on_platform(velocity: Velocity, standing_at: Vec3) -> (Simulation, Entity) {
+    let mut sim = Simulation::default();
+    let platform = sim
+        .world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_xyz(0.0, -2.0, 0.0)),
+            Collider::cuboid(4.0, 0.25, 4.0),
+            RigidBody::KinematicVelocityBased,
+            Velocity::zero(),
+        ))
+        .id();
+    sim.place_character(standing_at + Vec3::new(0.0, -0.75, 0.0));
+    sim.step(30);
+    assert!(sim.ground_sensor().grounded());
+    *sim.world_mut().get_mut::<Velocity>(platform).unwrap() = velocity;
+    (sim, platform)
+}
+
+fn platform_position(sim: &Simulation, platform: Entity) -> Vec3 {
+    sim.world().get::<Transform>(platform).unwrap().translation
+}
+
+#[test]
+fn characters_ride_moving_platforms() {
+    let (mut sim, platform) = on_platform(Velocity::linear(Vec3::X * 2.0), Vec3::ZERO);
+    let offset = sim.transform().translation - platform_position(&sim, platform);
+    sim.step(90);
+
+    assert!(sim.ground_sensor().grounded());
+    let moved = platform_position(&sim, platform).x;
+    assert!(moved > 2.5, "platform only moved {moved}");
+    let drift = sim.transform().translation - platform_position(&sim, platform) - offset;
+    assert!(drift.xz().length() < 0.1, "drifted {drift}");
+}
+
+#[test]
+fn rotating_platforms_carry_and_turn_the_character() {
+    let (mut sim, _) = on_platform(Velocity::angular(Vec3::Y), Vec3::new(2.0, 0.0, 0.0));
+    let facing = sim.transform().forward();
+    sim.step(90);
+
+    let position = sim.transform().translation.xz();
+    assert!((position.length() - 2.0).abs() < 0.1, "{position}");
+    // Turned by the same 1.5 rad as the platform
+    let expected = Quat::from_rotation_y(1.5) * Vec3::new(2.0, 0.0, 0.0);
+    assert!(
+        (position - expected.xz()).length() < 0.15,
+        "{position} vs {expected}"
+    );
+    let turned = facing.angle_between(sim.transform().forward());
+    assert!((turned - 1.5).abs() < 0.05, "{turned}");
+}
+
+#[test]
+fn jumping_off_carries_the_platform_velocity_for_a_moment() {
+    let (mut sim, platform) = on_platform(Velocity::linear(Vec3::X * 3.0), Vec3::ZERO);
+    sim.step(10);
+    sim.press(PlayerAction::Jump);
+    sim.step(5);
+    assert!(!sim.ground_sensor().grounded());
+
+    // Stop the platform, the character keeps going in the air but slows down
+    *sim.world_mut().get_mut::<Velocity>(platform).unwrap() = Velocity::zero();
+    let before = sim.transform().translation.x;
+    sim.step(10);
+    let carried = sim.transform().translation.x - before;
+    assert!(carried > 0.2 && carried < 0.5, "carried {carried}");
+    let fading = sim.forces().get_vector(ForceId::Platform).unwrap();
+    assert!(fading.x > 0.0 && fading.x < 3.0, "{fading}");
+
+    sim.step(20);
+    assert!(!sim.forces().has_key(ForceId::Platform));
+    // Landing back on the now still platform leaves nothing behind
+    sim.step_until(120, |sim| sim.ground_sensor().grounded())
+        .expect("never landed");
+    sim.step(1);
+    assert!(!sim.forces().has_key(ForceId::Platform));
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use phys64::{
+    harness::Simulation,
+    prelude::*,
+    replay::{ReplayError, REPLAY_VERSION},
+};
+
+fn run_and_jump() -> Replay {
+    let run = InputFrame::new().with_move_axis(Vec2::new(0.0, 1.0));
+    let mut frames = vec![run.clone(); 40];
+    frames.extend(vec![run.clone().holding(PlayerAction::Jump); 15]);
+    frames.extend(vec![run; 30]);
+    Replay::new(1.0 / 60.0, frames)
+}
+
+#[test]
+fn ron_round_trip() {
+    let replay = run_and_jump();
+    let source = replay.to_ron().unwrap();
+
+    assert_eq!(Replay::from_ron(&source).unwrap(), replay);
+}
+
+#[test]
+fn rejects_other_versions() {
+    let mut replay = run_and_jump();
+    replay.version = REPLAY_VERSION + 1;
+    let source = replay.to_ron().unwrap();
+
+    assert!(matches!(
+        Replay::from_ron(&source),
+        Err(ReplayError::UnsupportedVersion(version)) if version == REPLAY_VERSION + 1
+    ));
+}
+
+#[test]
+fn loaded_replay_matches_the_original_run() {
+    let replay = run_and_jump();
+    let loaded = Replay::from_ron(&replay.to_ron().unwrap()).unwrap();
+    let ticks = replay.frames.len() as u32;
+
+    let mut original = Simulation::on_flat_ground();
+    original.play(&replay);
+    original.step(ticks);
+
+    let mut played_back = Simulation::on_flat_ground();
+    played_back.play(&loaded);
+    played_back.step(ticks);
+
+    assert_eq!(original.transform(), played_back.transform());
+    assert!(original.transform().translation.z < -5.0);
+}
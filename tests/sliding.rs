@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Runs forward over a floor tilted by `tilt` degrees about X and starts a slide. The character
+/// runs towards -Z, so negative tilts make it slide uphill.
+fn sliding_on(tilt: f32) -> Simulation {
+    let mut sim = Simulation::default();
+    sim.spawn_box(
+        Vec3::new(50.0, 0.25, 50.0),
+        Transform::from_xyz(0.0, -2.0, 0.0)
+            .with_rotation(Quat::from_rotation_x(-tilt.to_radians())),
+    );
+    sim.place_character(Vec3::new(0.0, -0.5, 0.0));
+    sim.step(30);
+    sim.set_move_axis(Vec2::Y);
+    sim.step(30);
+    sim.press(PlayerAction::Crouch);
+    sim.step(1);
+    assert!(sim.world().get::<Sliding>(sim.character()).is_some());
+    assert_eq!(sim.events::<SlideStarted>().len(), 1);
+    sim.set_move_axis(Vec2::ZERO);
+    sim
+}
+
+fn slide_speed(sim: &Simulation) -> f32 {
+    sim.forces()
+        .get_vector(ForceId::Slide)
+        .map_or(0.0, |slide| slide.xz().length())
+}
+
+#[test]
+fn slides_wear_off_on_flat_ground() {
+    let mut sim = sliding_on(0.0);
+    let mut ended = Vec::new();
+    sim.step_until(600, |sim| {
+        ended.extend(sim.events::<SlideEnded>());
+        !ended.is_empty()
+    })
+    .expect("the slide never ended");
+    assert_eq!(ended.len(), 1);
+    assert_eq!(ended[0].entity, sim.character());
+    assert!(sim.world().get::<Sliding>(sim.character()).is_none());
+    assert!(!sim.forces().has_key(ForceId::Slide));
+
+    // Still holding Crouch doesn't start another slide
+    sim.set_move_axis(Vec2::Y);
+    sim.step(30);
+    assert!(sim.world().get::<Sliding>(sim.character()).is_none());
+}
+
+#[test]
+fn slopes_turn_an_uphill_slide_around() {
+    // Sliding up a slope that descends towards +Z
+    let mut sim = sliding_on(-20.0);
+    let uphill = slide_speed(&sim);
+    sim.step(120);
+
+    assert!(sim.world().get::<Sliding>(sim.character()).is_some());
+    let slide = sim.forces().get_vector(ForceId::Slide).unwrap();
+    assert!(slide.z > 0.0, "{slide}");
+    assert!(
+        slide_speed(&sim) > 2.0,
+        "{} after {uphill}",
+        slide_speed(&sim)
+    );
+}
+
+#[test]
+fn steering_turns_the_slide_a_little() {
+    let mut sim = sliding_on(0.0);
+    let before = sim.forces().get_vector(ForceId::Slide).unwrap();
+    sim.set_move_axis(Vec2::X);
+    sim.step(30);
+    let after = sim.forces().get_vector(ForceId::Slide).unwrap();
+
+    let turned = before.angle_between(after);
+    let limit = SlideTuning::default().steering * 0.5;
+    assert!(after.x > 0.0, "{after}");
+    assert!(turned > limit * 0.5 && turned <= limit + 0.01, "{turned}");
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Stands the character on the 30° slope from the demo, which rises towards +X
+fn on_slope(max_walkable_angle: f32) -> Simulation {
+    let mut sim = Simulation::default();
+    sim.spawn_box(
+        Vec3::new(50.0, 0.25, 50.0),
+        Transform::from_xyz(0.0, -2.0, 0.0)
+            .with_rotation(Quat::from_axis_angle(Vec3::Z, 30.0_f32.to_radians())),
+    );
+    let character = sim.character();
+    let sensor = GroundSensor::default().with_slopes(SlopeTuning {
+        max_walkable_angle,
+        ..default()
+    });
+    sim.world_mut().entity_mut(character).insert(sensor);
+    sim.place_character(Vec3::new(0.0, -0.4, 0.0));
+    sim.step(10);
+    assert!(sim.ground_sensor().grounded());
+    sim
+}
+
+fn jump_rise(sim: &mut Simulation) -> f32 {
+    let start = sim.transform().translation.y;
+    let mut peak = start;
+    sim.press(PlayerAction::Jump);
+    sim.step_with(60, |sim| peak = peak.max(sim.transform().translation.y));
+    peak - start
+}
+
+#[test]
+fn steep_slopes_cannot_be_climbed() {
+    let mut walkable = on_slope(45.0);
+    let start = walkable.transform().translation.x;
+    walkable.set_move_axis(Vec2::X);
+    walkable.step(60);
+    let climbed = walkable.transform().translation.x - start;
+    assert!(climbed > 1.0, "climbed {climbed}");
+
+    let mut steep = on_slope(25.0);
+    let start = steep.transform().translation.x;
+    steep.set_move_axis(Vec2::X);
+    steep.step(60);
+    let climbed = steep.transform().translation.x - start;
+    assert!(climbed < 0.0, "climbed {climbed}");
+    assert!(!steep.forces().has_key(ForceId::Run));
+}
+
+#[test]
+fn steep_slopes_slide_the_character_down() {
+    let mut walkable = on_slope(45.0);
+    let start = walkable.transform().translation;
+    walkable.step(60);
+    let moved = (walkable.transform().translation - start).length();
+    assert!(moved < 0.1, "moved {moved}");
+
+    let mut steep = on_slope(25.0);
+    assert!(steep.ground_sensor().on_steep_slope());
+    let start = steep.transform().translation;
+    steep.step(30);
+    let early = steep.forces().get_vector(ForceId::Slope).unwrap();
+    steep.step(30);
+    let late = steep.forces().get_vector(ForceId::Slope).unwrap();
+
+    let downhill = steep.ground_sensor().get_slope_gradient().normalize();
+    assert!(
+        late.normalize().dot(downhill) > 0.99,
+        "{late} vs {downhill}"
+    );
+    assert!(late.length() > early.length());
+    let moved = steep.transform().translation - start;
+    assert!(moved.x < -1.0 && moved.y < -0.5, "moved {moved}");
+}
+
+#[test]
+fn jumps_off_steep_slopes_are_weak() {
+    let mut walkable = on_slope(45.0);
+    let full = jump_rise(&mut walkable);
+
+    let mut steep = on_slope(25.0);
+    let weak = jump_rise(&mut steep);
+    assert!(weak < full * 0.6, "{weak} vs {full}");
+    // Landing the hop doesn't count towards a double jump
+    assert!(steep.ground_sensor().grounded());
+    assert_eq!(*steep.component::<Jumper>().stage(), JumpStage::Single);
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+fn standing_on(surface: Option<SurfaceMaterial>) -> Simulation {
+    let mut sim = Simulation::default();
+    let floor = sim.spawn_box(
+        Vec3::new(100.0, 0.25, 100.0),
+        Transform::from_xyz(0.0, -2.0, 0.0),
+    );
+    if let Some(surface) = surface {
+        sim.world_mut().entity_mut(floor).insert(surface);
+    }
+    sim.place_character(Vec3::new(0.0, -0.75, 0.0));
+    sim.step(30);
+    sim
+}
+
+fn run_distance(surface: Option<SurfaceMaterial>) -> f32 {
+    let mut sim = standing_on(surface);
+    sim.set_move_axis(Vec2::Y);
+    sim.step(120);
+    sim.transform().translation.xz().length()
+}
+
+/// Distance covered after letting go of the stick at full speed
+fn skid_distance(surface: Option<SurfaceMaterial>) -> f32 {
+    let mut sim = standing_on(surface);
+    sim.set_move_axis(Vec2::Y);
+    sim.step(180);
+    sim.set_move_axis(Vec2::ZERO);
+    let start = sim.transform().translation;
+    sim.step(120);
+    (sim.transform().translation - start).xz().length()
+}
+
+#[test]
+fn sand_slows_the_run_down() {
+    let plain = run_distance(None);
+    let sand = run_distance(Some(SurfaceMaterial::SAND));
+    assert!(sand < plain * 0.7, "{sand} vs {plain}");
+
+    let sim = standing_on(Some(SurfaceMaterial::SAND));
+    assert_eq!(*sim.ground_sensor().surface(), SurfaceMaterial::SAND);
+}
+
+#[test]
+fn ice_keeps_a_long_skid() {
+    let plain = skid_distance(None);
+    let ice = skid_distance(Some(SurfaceMaterial::ICE));
+    assert!(ice > plain * 4.0, "{ice} vs {plain}");
+}
+
+#[test]
+fn ice_turns_slowly() {
+    let turned = |surface| {
+        let mut sim = standing_on(surface);
+        sim.set_move_axis(Vec2::Y);
+        sim.step(60);
+        let facing = sim.transform().forward();
+        sim.set_move_axis(Vec2::X);
+        sim.step(10);
+        facing.angle_between(sim.transform().forward())
+    };
+    let plain = turned(None);
+    let ice = turned(Some(SurfaceMaterial::ICE));
+    assert!(ice < plain * 0.5, "{ice} vs {plain}");
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*, Phys64Plugin};
+
+fn gravity(sim: &Simulation) -> Vec3 {
+    sim.forces().get_vector(ForceId::Gravity).unwrap()
+}
+
+#[test]
+fn long_falls_level_off_at_terminal_velocity() {
+    let mut sim = Simulation::new(Phys64Plugin::default().with_terminal_velocity(20.0));
+    let mut clamped = 0;
+    sim.step_with(240, |sim| {
+        clamped += sim
+            .events::<ForceChanged>()
+            .iter()
+            .filter(|event| {
+                event.force_id == ForceId::Gravity && event.reason == ForceChangeReason::Clamped
+            })
+            .count();
+    });
+
+    assert!(
+        (gravity(&sim).length() - 20.0).abs() < 1e-4,
+        "{}",
+        gravity(&sim)
+    );
+    assert!(clamped > 0);
+    let before = sim.transform().translation.y;
+    sim.step(60);
+    let fallen = before - sim.transform().translation.y;
+    assert!((fallen - 20.0).abs() < 0.1, "fell {fallen}");
+}
+
+#[test]
+fn entities_can_fall_at_their_own_speed() {
+    let mut sim = Simulation::default();
+    let character = sim.character();
+    sim.world_mut()
+        .entity_mut(character)
+        .insert(TerminalVelocity(Some(8.0)));
+    sim.step(120);
+    assert!(
+        (gravity(&sim).length() - 8.0).abs() < 1e-4,
+        "{}",
+        gravity(&sim)
+    );
+
+    // A new cap applies right away, even mid-fall
+    sim.world_mut()
+        .entity_mut(character)
+        .insert(TerminalVelocity(Some(4.0)));
+    sim.step(1);
+    assert!(
+        (gravity(&sim).length() - 4.0).abs() < 1e-4,
+        "{}",
+        gravity(&sim)
+    );
+
+    // The default hands the cap back to the Gravity resource rather than lifting it
+    sim.world_mut()
+        .entity_mut(character)
+        .insert(TerminalVelocity::default());
+    sim.step(180);
+    assert!(
+        (gravity(&sim).length() - 50.0).abs() < 1e-4,
+        "{}",
+        gravity(&sim)
+    );
+}
+
+#[test]
+fn forces_and_their_sum_can_be_capped() {
+    let mut force = Force::new(Vec3::X * 3.0, None, ForceDecayType::Manual).with_max_magnitude(4.0);
+    assert!(!force.add_force(Vec3::X));
+    assert!(force.add_force(Vec3::X));
+    assert_eq!(force.current(), Vec3::X * 4.0);
+
+    let mut forces = Forces::default().with_max_combined(5.0);
+    forces.add(
+        ForceId::Wind,
+        Force::new(Vec3::X * 3.0, None, ForceDecayType::Manual),
+    );
+    assert!(!forces.is_clamped());
+    forces.add(
+        ForceId::Knockback,
+        Force::new(Vec3::Z * 4.0, None, ForceDecayType::Manual),
+    );
+    forces.add(
+        ForceId::Gravity,
+        Force::new(Vec3::NEG_Y * 12.0, None, ForceDecayType::Manual),
+    );
+    assert!(forces.is_clamped());
+    assert!((forces.get_combined_force().length() - 5.0).abs() < 1e-5);
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*, Phys64Plugin};
+
+const RATES: [f64; 3] = [30.0, 60.0, 120.0];
+
+fn standing_at(rate: f64) -> Simulation {
+    let mut sim = Simulation::new(Phys64Plugin::default().with_timestep(1.0 / rate));
+    sim.spawn_box(
+        Vec3::new(200.0, 0.25, 200.0),
+        Transform::from_xyz(0.0, -2.0, 0.0),
+    );
+    sim.place_character(Vec3::new(0.0, -0.75, 0.0));
+    sim.step(rate as u32 / 2);
+    sim
+}
+
+fn within(values: &[f32], tolerance: f32) -> bool {
+    let reference = values[1];
+    values
+        .iter()
+        .all(|value| (value - reference).abs() <= reference.abs() * tolerance)
+}
+
+fn jump_apex(rate: f64) -> f32 {
+    let mut sim = standing_at(rate);
+    let start = sim.transform().translation.y;
+    let mut peak = start;
+    sim.press(PlayerAction::Jump);
+    sim.step_with(rate as u32, |sim| {
+        peak = peak.max(sim.transform().translation.y);
+    });
+    peak - start
+}
+
+fn run_distance(rate: f64) -> f32 {
+    let mut sim = standing_at(rate);
+    let start = sim.transform().translation;
+    sim.set_move_axis(Vec2::Y);
+    sim.step(rate as u32 * 2);
+    (sim.transform().translation - start).xz().length()
+}
+
+#[test]
+fn jump_apex_holds_across_tick_rates() {
+    let apexes = RATES.map(jump_apex);
+    assert!(within(&apexes, 0.05), "{apexes:?}");
+}
+
+#[test]
+fn run_distance_holds_across_tick_rates() {
+    let distances = RATES.map(run_distance);
+    assert!(within(&distances, 0.02), "{distances:?}");
+}
+
+#[test]
+fn slopes_hold_the_character_at_any_tick_rate() {
+    let rests = RATES.map(|rate| {
+        let mut sim = Simulation::new(Phys64Plugin::default().with_timestep(1.0 / rate));
+        sim.spawn_box(
+            Vec3::new(50.0, 0.25, 50.0),
+            Transform::from_xyz(0.0, -2.0, 0.0)
+                .with_rotation(Quat::from_rotation_z(20.0_f32.to_radians())),
+        );
+        sim.place_character(Vec3::new(0.0, -0.4, 0.0));
+        sim.step(rate as u32 / 4);
+        let settled = sim.transform().translation;
+        sim.step(rate as u32);
+        let moved = sim.transform().translation.distance(settled);
+        assert!(moved < 0.01, "slid {moved} at {rate} Hz");
+        settled.y
+    });
+    assert!(within(&rests, 0.01), "{rests:?}");
+}
+
+#[test]
+fn falls_match_constant_acceleration() {
+    let drops = RATES.map(|rate| {
+        let mut sim = Simulation::new(Phys64Plugin::default().with_timestep(1.0 / rate));
+        let start = sim.transform().translation.y;
+        sim.step(rate as u32);
+        start - sim.transform().translation.y
+    });
+    // Half of 24 units/s² over one second
+    assert!(
+        drops.iter().all(|drop| (drop - 12.0).abs() < 0.05),
+        "{drops:?}"
+    );
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+/// Runs at a wall 2.5 units ahead, jumps, and stops once the wall sensor reports contact
+fn jump_at_wall() -> (Simulation, Entity) {
+    let mut sim = Simulation::on_flat_ground();
+    let wall = sim.spawn_box(
+        Vec3::new(5.0, 5.0, 0.25),
+        Transform::from_xyz(0.0, 3.0, -2.75),
+    );
+    sim.set_move_axis(Vec2::Y);
+    sim.step(10);
+    sim.press(PlayerAction::Jump);
+    sim.step(1);
+    sim.release(PlayerAction::Jump);
+    sim.step_until(120, |sim| sim.component::<WallSensor>().contact().is_some())
+        .expect("never reached the wall");
+    (sim, wall)
+}
+
+fn kick(sim: &mut Simulation) {
+    sim.press(PlayerAction::Jump);
+    sim.step(1);
+    sim.release(PlayerAction::Jump);
+}
+
+#[test]
+fn kicking_off_a_wall_reverses_and_lifts() {
+    let (mut sim, wall) = jump_at_wall();
+    assert!(!sim.ground_sensor().grounded());
+    kick(&mut sim);
+
+    let kick_force = sim.forces().get_vector(ForceId::WallKick).expect("no kick");
+    assert!(kick_force.z > 0.0, "{kick_force}");
+    assert!(sim.momentum().y > 0.0);
+    assert_eq!(sim.component::<WallSensor>().kicked_wall(), Some(wall));
+
+    sim.step(1);
+    assert_eq!(sim.world().resource::<PlayerData>().kicked_wall, Some(wall));
+
+    let start = sim.transform().translation;
+    sim.step(20);
+    assert!(sim.transform().translation.z > start.z + 1.0);
+}
+
+#[test]
+fn the_same_wall_cannot_be_kicked_twice_before_landing() {
+    let (mut sim, _) = jump_at_wall();
+    let contact = sim.component::<WallSensor>().contact().unwrap();
+    kick(&mut sim);
+    let first_kick = sim.forces().get_vector(ForceId::WallKick).expect("no kick");
+
+    // Touch the same wall again mid-air
+    let character = sim.character();
+    sim.world_mut()
+        .get_mut::<WallSensor>(character)
+        .unwrap()
+        .touch(contact);
+    assert!(sim.component::<WallSensor>().kickable().is_none());
+    kick(&mut sim);
+    assert_eq!(sim.forces().get_vector(ForceId::WallKick), Some(first_kick));
+
+    sim.step_until(180, |sim| sim.ground_sensor().grounded())
+        .expect("never landed");
+    assert_eq!(sim.component::<WallSensor>().kicked_wall(), None);
+    assert!(sim.forces().get_vector(ForceId::WallKick).is_none());
+}
+
+#[test]
+fn one_press_kicks_only_one_wall() {
+    let (mut sim, wall) = jump_at_wall();
+    let other_wall = sim.spawn_box(
+        Vec3::new(0.25, 5.0, 5.0),
+        Transform::from_xyz(-3.0, 3.0, 0.0),
+    );
+    sim.press(PlayerAction::Jump);
+    sim.step(1);
+    let first_kick = sim.forces().get_vector(ForceId::WallKick).expect("no kick");
+
+    // Meet a second wall with jump still held down
+    let character = sim.character();
+    sim.world_mut()
+        .get_mut::<WallSensor>(character)
+        .unwrap()
+        .touch(WallContact {
+            wall: other_wall,
+            normal: Vec3::X,
+        });
+    sim.step(1);
+    sim.release(PlayerAction::Jump);
+    assert_eq!(sim.forces().get_vector(ForceId::WallKick), Some(first_kick));
+    assert_eq!(sim.component::<WallSensor>().kicked_wall(), Some(wall));
+}
+
+#[test]
+fn missing_the_window_does_nothing() {
+    let (mut sim, _) = jump_at_wall();
+    sim.set_move_axis(Vec2::NEG_Y);
+    sim.step(30);
+    kick(&mut sim);
+    assert!(sim.forces().get_vector(ForceId::WallKick).is_none());
+}
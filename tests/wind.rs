@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use phys64::{harness::Simulation, prelude::*};
+
+fn spawn_zone(sim: &mut Simulation, half_extents: Vec3, translation: Vec3, zone: WindZone) {
+    sim.world_mut().spawn((
+        TransformBundle::from_transform(Transform::from_translation(translation)),
+        Collider::cuboid(half_extents.x, half_extents.y, half_extents.z),
+        Sensor,
+        zone,
+    ));
+}
+
+#[test]
+fn zones_blow_until_left() {
+    let mut sim = Simulation::on_flat_ground();
+    spawn_zone(
+        &mut sim,
+        Vec3::new(2.0, 3.0, 2.0),
+        Vec3::ZERO,
+        WindZone::new(Vec3::X, 4.0),
+    );
+    sim.step(2);
+    assert_eq!(sim.forces().get_vector(ForceId::Wind), Some(Vec3::X * 4.0));
+
+    sim.step_until(120, |sim| {
+        sim.events::<ForceChanged>().iter().any(|event| {
+            event.force_id == ForceId::Wind && event.reason == ForceChangeReason::Removed
+        })
+    })
+    .expect("the wind never let go");
+    assert!(!sim.forces().has_key(ForceId::Wind));
+    let x = sim.transform().translation.x;
+    assert!(x > 2.0, "{x}");
+}
+
+#[test]
+fn overlapping_zones_add_up() {
+    let mut sim = Simulation::on_flat_ground();
+    let half_extents = Vec3::splat(10.0);
+    spawn_zone(
+        &mut sim,
+        half_extents,
+        Vec3::ZERO,
+        WindZone::new(Vec3::X, 3.0),
+    );
+    spawn_zone(
+        &mut sim,
+        half_extents,
+        Vec3::ZERO,
+        WindZone::new(Vec3::Z, 4.0),
+    );
+    sim.step(2);
+    let wind = sim.forces().get_vector(ForceId::Wind).unwrap();
+    assert!((wind - Vec3::new(3.0, 0.0, 4.0)).length() < 1e-4, "{wind}");
+}
+
+#[test]
+fn scaled_zones_stretch_their_falloff() {
+    let mut sim = Simulation::on_flat_ground();
+    sim.world_mut().spawn((
+        TransformBundle::from_transform(
+            Transform::from_xyz(6.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+        ),
+        Collider::cuboid(5.0, 5.0, 5.0),
+        Sensor,
+        WindZone::new(Vec3::X, 8.0).with_falloff(WindFalloff::Radial { radius: 4.0 }),
+    ));
+    sim.step(2);
+    // 6 units from the origin of a zone scaled by 2 is 3 of the zone's own
+    let wind = sim
+        .forces()
+        .get_vector(ForceId::Wind)
+        .expect("not in the zone");
+    assert!((wind - Vec3::X * 2.0).length() < 0.1, "{wind}");
+}
+
+#[test]
+fn rotated_zones_turn_their_wind() {
+    let mut sim = Simulation::on_flat_ground();
+    // Blows along -Z once turned, with the character 4 units downwind of the origin
+    sim.world_mut().spawn((
+        TransformBundle::from_transform(
+            Transform::from_xyz(0.0, 0.0, 4.0)
+                .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)),
+        ),
+        Collider::cuboid(5.0, 5.0, 5.0),
+        Sensor,
+        WindZone::new(Vec3::X, 8.0).with_falloff(WindFalloff::Fan { length: 8.0 }),
+    ));
+    sim.step(2);
+    let wind = sim
+        .forces()
+        .get_vector(ForceId::Wind)
+        .expect("not in the zone");
+    assert!((wind - Vec3::NEG_Z * 4.0).length() < 0.1, "{wind}");
+}
+
+#[test]
+fn falloff_turbulence_and_airborne_scale_shape_the_wind() {
+    let zone = WindZone::new(Vec3::X, 10.0)
+        .with_falloff(WindFalloff::Fan { length: 8.0 })
+        .with_airborne_scale(2.0);
+    assert_eq!(zone.force_at(Vec3::ZERO, 0.0, false), Vec3::X * 10.0);
+    assert_eq!(zone.force_at(Vec3::X * 4.0, 0.0, false), Vec3::X * 5.0);
+    assert_eq!(zone.force_at(Vec3::X * 4.0, 0.0, true), Vec3::X * 10.0);
+    assert_eq!(zone.force_at(Vec3::X * 9.0, 0.0, false), Vec3::ZERO);
+    // A zero sized falloff leaves no wind rather than dividing by zero
+    for falloff in [
+        WindFalloff::Radial { radius: 0.0 },
+        WindFalloff::Fan { length: 0.0 },
+    ] {
+        assert_eq!(falloff.scale(Vec3::ZERO, Vec3::X), 0.0);
+        assert_eq!(falloff.scale(Vec3::X, Vec3::X), 0.0);
+    }
+
+    let gusty = WindZone::new(Vec3::X, 10.0).with_turbulence(0.5);
+    let strengths: Vec<f32> = (0..600)
+        .map(|tick| gusty.force_at(Vec3::ZERO, tick as f32 / 60.0, false).x)
+        .collect();
+    assert!(strengths
+        .iter()
+        .all(|strength| (5.0..=15.0).contains(strength)));
+    assert!(strengths
+        .iter()
+        .any(|strength| (strength - 10.0).abs() > 1.0));
+}